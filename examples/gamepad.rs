@@ -19,7 +19,7 @@ fn main() {
     app.add_plugins((
         // This plugin contains all the code from the original example
         GamepadViewerExample,
-        InputCapturePlugin,
+        InputCapturePlugin::default(),
         InputPlaybackPlugin,
     ))
     // Toggle between playback and capture using Space
@@ -68,7 +68,7 @@ fn toggle_capture_vs_playback(
                 commands.trigger(EndInputPlayback);
                 // Enable input capture
                 commands.trigger(BeginInputCapture {
-                    filepath: Some("./data/hello_world.ron".to_string()),
+                    filepath: Some("./data/hello_world.ron".into()),
                     ..default()
                 });
 
@@ -183,16 +183,10 @@ mod gamepad_viewer_example {
         fn from_world(world: &mut World) -> Self {
             let mut meshes = world.resource_mut::<Assets<Mesh>>();
             Self {
-                circle: meshes.add(Circle::new(BUTTON_RADIUS).mesh()).into(),
-                triangle: meshes
-                    .add(RegularPolygon::new(BUTTON_RADIUS, 3).mesh())
-                    .into(),
-                start_pause: meshes
-                    .add(Rectangle::new(START_SIZE.x, START_SIZE.y).mesh())
-                    .into(),
-                trigger: meshes
-                    .add(Rectangle::new(TRIGGER_SIZE.x, TRIGGER_SIZE.y).mesh())
-                    .into(),
+                circle: meshes.add(Circle::new(BUTTON_RADIUS).mesh()),
+                triangle: meshes.add(RegularPolygon::new(BUTTON_RADIUS, 3).mesh()),
+                start_pause: meshes.add(Rectangle::new(START_SIZE.x, START_SIZE.y).mesh()),
+                trigger: meshes.add(Rectangle::new(TRIGGER_SIZE.x, TRIGGER_SIZE.y).mesh()),
             }
         }
     }
@@ -6,7 +6,7 @@ use leafwing_input_playback::{
 
 fn main() -> AppExit {
     let mut app = App::new();
-    app.add_plugins((DefaultPlugins, InputCapturePlugin));
+    app.add_plugins((DefaultPlugins, InputCapturePlugin::default()));
     app.add_systems(Update, debug_input_capture);
     app.world_mut().trigger(BeginInputCapture::default());
     app.run()
@@ -8,16 +8,20 @@ use leafwing_input_playback::{
 
 fn main() -> AppExit {
     let mut app = App::new();
-    app.add_plugins((DefaultPlugins, InputCapturePlugin, InputPlaybackPlugin))
-        // Creates a little game that spawns decaying boxes where the player clicks
-        .insert_resource(ClearColor(Color::srgb(0.9, 0.9, 0.9)))
-        // Toggle between playback and capture by pressing Space
-        .insert_resource(InputStrategy::Playback)
-        .add_systems(Startup, setup)
-        .add_systems(
-            Update,
-            (spawn_boxes, decay_boxes, toggle_capture_vs_playback),
-        );
+    app.add_plugins((
+        DefaultPlugins,
+        InputCapturePlugin::default(),
+        InputPlaybackPlugin,
+    ))
+    // Creates a little game that spawns decaying boxes where the player clicks
+    .insert_resource(ClearColor(Color::srgb(0.9, 0.9, 0.9)))
+    // Toggle between playback and capture by pressing Space
+    .insert_resource(InputStrategy::Playback)
+    .add_systems(Startup, setup)
+    .add_systems(
+        Update,
+        (spawn_boxes, decay_boxes, toggle_capture_vs_playback),
+    );
     app.run()
 }
 
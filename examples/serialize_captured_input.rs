@@ -8,14 +8,14 @@ use leafwing_input_playback::input_capture::{
 
 fn main() {
     let mut app = App::new();
-    app.add_plugins((DefaultPlugins, InputCapturePlugin))
+    app.add_plugins((DefaultPlugins, InputCapturePlugin::default()))
         .add_systems(Last, trigger_input_capture_on_exit);
     app.world_mut().trigger(BeginInputCapture {
         input_modes_captured: InputModesCaptured {
             keyboard: true,
             ..InputModesCaptured::DISABLE_ALL
         },
-        filepath: Some("./data/test_playback.ron".to_string()),
+        filepath: Some("./data/test_playback.ron".into()),
         ..Default::default()
     });
     app.run();
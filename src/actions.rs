@@ -0,0 +1,155 @@
+//! Converts captured input into timelines of triggered actions, for analysis.
+//!
+//! Recordings are replayed against an [`InputMap`] from `leafwing-input-manager`,
+//! so they can be analyzed in terms of the same gameplay actions that the app binds.
+
+use bevy::input::ButtonState;
+use bevy::utils::{Duration, HashSet};
+use leafwing_input_manager::buttonlike::ButtonValue;
+use leafwing_input_manager::clashing_inputs::ClashStrategy;
+use leafwing_input_manager::input_map::InputMap;
+use leafwing_input_manager::user_input::updating::CentralInputStore;
+use leafwing_input_manager::Actionlike;
+
+use crate::timestamped_input::{ButtonlikeInput, TimestampedInputs};
+
+/// Replays the `inputs` against the `map`, returning the time at which each action was pressed.
+///
+/// An action is pressed when its bindings go from released to pressed,
+/// matching the `just_pressed` semantics of `leafwing-input-manager`.
+/// Clashing bindings are resolved using the default [`ClashStrategy`].
+/// Only keyboard and mouse buttons are replayed: gamepad buttons are ignored.
+/// Entries are returned in the order they were recorded.
+pub fn actions_over_time<A: Actionlike>(
+    inputs: &TimestampedInputs,
+    map: &InputMap<A>,
+) -> Vec<(Duration, A)> {
+    let mut held: HashSet<ButtonlikeInput> = HashSet::default();
+    let mut pressed_actions: HashSet<A> = HashSet::default();
+    let mut input_store = CentralInputStore::default();
+    let mut timeline = Vec::new();
+
+    for event in &inputs.events {
+        let Some((input, state)) = event.input_event.buttonlike_state() else {
+            continue;
+        };
+        if let ButtonlikeInput::GamepadButton(..) = input {
+            continue;
+        }
+
+        match state {
+            ButtonState::Pressed => held.insert(input),
+            ButtonState::Released => held.remove(&input),
+        };
+
+        input_store.clear();
+        for held_input in &held {
+            match *held_input {
+                ButtonlikeInput::Keyboard(key_code) => {
+                    input_store.update_buttonlike(key_code, ButtonValue::from_pressed(true));
+                }
+                ButtonlikeInput::MouseButton(button) => {
+                    input_store.update_buttonlike(button, ButtonValue::from_pressed(true));
+                }
+                ButtonlikeInput::GamepadButton(..) => {}
+            }
+        }
+
+        let updated_actions = map.process_actions(None, &input_store, ClashStrategy::default());
+        let now_pressed: HashSet<A> = map
+            .buttonlike_actions()
+            .filter(|action| updated_actions.pressed(action))
+            .cloned()
+            .collect();
+
+        timeline.extend(
+            map.buttonlike_actions()
+                .filter(|action| {
+                    now_pressed.contains(*action) && !pressed_actions.contains(*action)
+                })
+                .map(|action| (event.time_since_startup, action.clone())),
+        );
+        pressed_actions = now_pressed;
+    }
+
+    timeline
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::core::FrameCount;
+    use bevy::ecs::entity::Entity;
+    use bevy::input::keyboard::{Key, KeyCode, KeyboardInput};
+    use bevy::input::mouse::MouseButton;
+    use bevy::reflect::Reflect;
+
+    use crate::timestamped_input::InputEvent;
+
+    #[derive(Actionlike, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+    enum Action {
+        Jump,
+        Shoot,
+    }
+
+    fn key(key_code: KeyCode, state: ButtonState) -> InputEvent {
+        InputEvent::Keyboard(KeyboardInput {
+            key_code,
+            logical_key: Key::Space,
+            state,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        })
+    }
+
+    #[test]
+    fn actions_fire_when_pressed() {
+        let mut map = InputMap::new([
+            (Action::Jump, KeyCode::Space),
+            (Action::Jump, KeyCode::KeyW),
+        ]);
+        map.insert(Action::Shoot, MouseButton::Left);
+
+        let mut inputs = TimestampedInputs::default();
+        inputs.send(
+            FrameCount(1),
+            Duration::from_secs(1),
+            key(KeyCode::Space, ButtonState::Pressed),
+        );
+        // Already held through another binding, so Jump is not pressed again
+        inputs.send(
+            FrameCount(2),
+            Duration::from_secs(2),
+            key(KeyCode::KeyW, ButtonState::Pressed),
+        );
+        inputs.send(
+            FrameCount(3),
+            Duration::from_secs(3),
+            key(KeyCode::Space, ButtonState::Released),
+        );
+        inputs.send(
+            FrameCount(4),
+            Duration::from_secs(4),
+            key(KeyCode::KeyW, ButtonState::Released),
+        );
+        // Unbound inputs are ignored
+        inputs.send(
+            FrameCount(5),
+            Duration::from_secs(5),
+            key(KeyCode::KeyQ, ButtonState::Pressed),
+        );
+        inputs.send(
+            FrameCount(6),
+            Duration::from_secs(6),
+            key(KeyCode::KeyW, ButtonState::Pressed),
+        );
+
+        assert_eq!(
+            actions_over_time(&inputs, &map),
+            vec![
+                (Duration::from_secs(1), Action::Jump),
+                (Duration::from_secs(6), Action::Jump),
+            ]
+        );
+    }
+}
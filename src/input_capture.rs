@@ -2,80 +2,400 @@
 //!
 //! These are unified into a single [`TimestampedInputs`](crate::timestamped_input::TimestampedInputs) resource, which can be played back.
 
-use bevy::app::{App, AppExit, Last, Plugin};
+use bevy::app::{App, AppExit, Last, Plugin, Startup};
 use bevy::core::{update_frame_count, FrameCount};
-use bevy::ecs::prelude::*;
-use bevy::input::gamepad::GamepadEvent;
-use bevy::input::keyboard::KeyboardInput;
-use bevy::input::mouse::{MouseButtonInput, MouseWheel};
+use bevy::ecs::{prelude::*, system::SystemParam};
+use bevy::input::gamepad::{GamepadEvent, GamepadRumbleRequest};
+use bevy::input::keyboard::{KeyCode, KeyboardInput};
+use bevy::input::mouse::{
+    AccumulatedMouseMotion, AccumulatedMouseScroll, MouseButtonInput, MouseWheel,
+};
+use bevy::input::{ButtonInput, ButtonState};
+use bevy::log::warn;
+use bevy::math::Vec2;
+#[cfg(feature = "states")]
+use bevy::state::prelude::{OnEnter, OnExit, State, States};
 use bevy::time::Time;
-use bevy::window::CursorMoved;
-use ron::ser::PrettyConfig;
+use bevy::utils::{Duration, HashMap, Instant};
+use bevy::window::{CursorMoved, Ime, PrimaryWindow, Window, WindowFocused, WindowMoved};
 
-use crate::serde::PlaybackFilePath;
-use crate::timestamped_input::TimestampedInputs;
+#[cfg(feature = "file")]
+use crate::serde::{append_to_streamed_recording, write_seekable_recording, PlaybackFilePath};
+use crate::timestamped_input::{
+    ButtonlikeInput, InputDevice, InputEvent, InputEventKind, Modifiers, RumbleRequest, SimTick,
+    TimestampedInputEvent, TimestampedInputs,
+};
+#[cfg(feature = "file")]
+use ron::ser::PrettyConfig;
+#[cfg(feature = "file")]
 use std::fs::OpenOptions;
+#[cfg(feature = "file")]
 use std::io::Write;
+#[cfg(feature = "file")]
+use std::path::PathBuf;
 
 /// Captures user inputs from the assorted raw `Event` types
 ///
 /// These are collected into a [`TimestampedInputs`](crate::timestamped_input::TimestampedInputs) resource.
 /// Which input modes (mouse, keyboard, etc) are captured is controlled via the [`InputModesCaptured`] resource.
 ///
-/// Input is serialized into the path stored in the [`PlaybackFilePath`] resource, if any.
-pub struct InputCapturePlugin;
+/// Input is serialized into the path stored in the [`PlaybackFilePath`](crate::serde::PlaybackFilePath) resource, if any.
+#[derive(Debug, Default)]
+pub struct InputCapturePlugin {
+    /// If set, this [`BeginInputCapture`] event is triggered on app startup, so that input capture begins automatically.
+    pub auto_begin: Option<BeginInputCapture>,
+}
 
 impl Plugin for InputCapturePlugin {
     fn build(&self, app: &mut App) {
-        app.add_observer(BeginInputCapture::observer)
+        app.init_resource::<CaptureState>()
+            .init_resource::<NamedRecordings>()
+            .init_resource::<ActiveNamedCaptures>()
+            .add_observer(BeginInputCapture::observer)
             .add_observer(EndInputCapture::observer)
+            .add_observer(PauseInputCapture::observer)
+            .add_observer(ResumeInputCapture::observer)
+            .add_observer(BeginNamedInputCapture::observer)
+            .add_observer(EndNamedInputCapture::observer)
             .add_systems(
                 Last,
                 (
+                    capture_accumulated_mouse.run_if(
+                        resource_exists::<CaptureAccumulatedMouse>
+                            .and(resource_exists::<TimestampedInputs>),
+                    ),
                     // Capture any mocked input as well
                     capture_input.run_if(resource_exists::<TimestampedInputs>),
+                    capture_named_input,
+                    record_metadata.run_if(
+                        resource_exists::<RecordingMetadata>
+                            .and(resource_exists::<TimestampedInputs>),
+                    ),
+                    end_capture_on_focus_loss.run_if(resource_exists::<EndCaptureOnFocusLoss>),
                     handle_final_capture_frame.run_if(resource_exists::<FinalCaptureFrame>),
+                    handle_final_capture_time.run_if(resource_exists::<FinalCaptureTime>),
                 )
                     .chain()
                     .before(update_frame_count),
             );
+
+        #[cfg(feature = "file")]
+        app.add_observer(FlushCapture::observer).add_systems(
+            Last,
+            stream_captured_input
+                .run_if(
+                    resource_exists::<StreamingCapture>
+                        .and(resource_exists::<TimestampedInputs>)
+                        .and(resource_exists::<PlaybackFilePath>),
+                )
+                .after(capture_input)
+                .before(handle_final_capture_frame),
+        );
+
+        #[cfg(feature = "picking")]
+        app.add_systems(
+            Last,
+            crate::picking::capture_pointer_events
+                .run_if(
+                    resource_exists::<crate::picking::CapturePointerEvents>
+                        .and(resource_exists::<TimestampedInputs>),
+                )
+                .before(capture_input),
+        );
+
+        if let Some(begin_input_capture) = self.auto_begin.clone() {
+            app.add_systems(Startup, move |mut commands: Commands| {
+                commands.trigger(begin_input_capture.clone());
+            });
+        }
     }
 }
 
 /// An Observer that users can trigger to initiate input capture.
 ///
+/// If mouse motion is captured, the current cursor position is recorded as the first event,
+/// so that playback begins with the cursor in the right place.
+///
 /// Data is serialized to the provided `filepath` when either an [`EndInputCapture`] or an [`AppExit`] event is detected.
-#[derive(Debug, Default, Event)]
+#[derive(Debug, Clone, Event)]
 pub struct BeginInputCapture {
     /// The input mechanisms that will be captured, see [`InputModesCaptured`].
     pub input_modes_captured: InputModesCaptured,
     /// The filepath at which to serialize captured input data.
-    pub filepath: Option<String>,
+    ///
+    /// This can be set from any path-like value with [`with_file`](Self::with_file).
+    #[cfg(feature = "file")]
+    pub filepath: Option<PathBuf>,
+    /// If set, captured input data is serialized as a seekable recording,
+    /// along with a `.idx` sidecar file with a checkpoint every this many events.
+    ///
+    /// See [`write_seekable_recording`] and [`SeekableRecording`](crate::serde::SeekableRecording).
+    #[cfg(feature = "file")]
+    pub index_checkpoint_interval: Option<usize>,
+    /// If set, captured input data is streamed to the `filepath` whenever this many events have been buffered,
+    /// rather than being written all at once when capture ends.
+    ///
+    /// See [`StreamingCapture`] and [`FlushCapture`].
+    #[cfg(feature = "file")]
+    pub stream_buffer_size: Option<usize>,
+    /// If `true`, captured input data is canonicalized before it is serialized,
+    /// so that equivalent captures produce byte-identical files.
+    ///
+    /// Has no effect on streamed captures, see [`CanonicalizeRecording`].
+    #[cfg(feature = "file")]
+    pub canonicalize: bool,
     /// The number of frames for which inputs should be captured.
     /// If None, inputs will be captured until an [`EndInputCapture`] or [`AppExit`] event is detected.
+    ///
+    /// This is always measured in rendered frames, even if [`use_sim_tick`](Self::use_sim_tick) is set.
+    /// If [`duration_to_capture`](Self::duration_to_capture) is also set, capture ends at whichever limit is reached first,
+    /// see [`stop_condition`](Self::stop_condition).
     pub frames_to_capture: Option<FrameCount>,
+    /// The amount of time for which inputs should be captured, measured by [`Time`].
+    /// If None, inputs will be captured until an [`EndInputCapture`] or [`AppExit`] event is detected.
+    ///
+    /// If [`frames_to_capture`](Self::frames_to_capture) is also set, capture ends at whichever limit is reached first,
+    /// see [`stop_condition`](Self::stop_condition).
+    pub duration_to_capture: Option<Duration>,
     /// A `Window` entity which acts as a filter for which inputs will be captured.
     /// This data will not be serialized, so that a target window can be selected on playback.
     pub window_to_capture: Option<Entity>,
+    /// Should inputs be captured while their window does not have focus?
+    ///
+    /// If `false`, inputs are dropped while their window is unfocused, see [`CaptureOnlyWhenFocused`].
+    /// Defaults to `true`.
+    pub capture_while_unfocused: bool,
+    /// If `true`, input capture is ended as soon as the captured window loses focus.
+    ///
+    /// See [`EndCaptureOnFocusLoss`].
+    pub end_on_focus_loss: bool,
+    /// Should newly captured inputs be appended to an existing [`TimestampedInputs`] resource?
+    ///
+    /// If `false`, any existing [`TimestampedInputs`] are discarded and a fresh recording is started.
+    /// Defaults to `true`.
+    pub preserve_existing: bool,
+    /// If `true`, keyboard and mouse button events are only captured when they change the state of their button.
+    ///
+    /// This drops repeated presses (and releases) of a button, see [`CaptureEdgesOnly`].
+    pub edge_only: bool,
+    /// If set, an [`InputEvent::IdleMarker`] is recorded whenever no inputs have been captured for this many frames.
+    ///
+    /// This preserves idle periods in the recording, see [`IdleMarkers`].
+    pub idle_marker_interval: Option<u64>,
+    /// If `true`, inputs are timestamped with the current [`SimTick`] rather than the [`FrameCount`].
+    ///
+    /// See [`CaptureSimTicks`].
+    pub use_sim_tick: bool,
+    /// If `true`, snapshots of the [`AccumulatedMouseMotion`] and [`AccumulatedMouseScroll`] resources are captured each frame.
+    ///
+    /// See [`CaptureAccumulatedMouse`].
+    pub capture_accumulated_mouse: bool,
+    /// If set, captured gamepad rumble requests are quantized to multiples of this intensity step,
+    /// and requests that would not change the quantized intensity are dropped.
+    ///
+    /// See [`RumbleQuantization`].
+    pub rumble_quantization: Option<f32>,
+    /// If `true`, [`bevy::picking`] pointer events are captured as well.
+    ///
+    /// See [`CapturePointerEvents`](crate::picking::CapturePointerEvents).
+    #[cfg(feature = "picking")]
+    pub capture_pointer_events: bool,
+    /// If `true`, each captured event is also stamped with a precise timestamp.
+    ///
+    /// See [`PreciseTimestamps`].
+    pub precise_timestamps: bool,
+    /// If `true`, captured keyboard and mouse events are annotated with the modifier keys that were held.
+    ///
+    /// See [`CaptureModifiers`].
+    pub capture_modifiers: bool,
+}
+
+impl Default for BeginInputCapture {
+    fn default() -> Self {
+        BeginInputCapture {
+            input_modes_captured: InputModesCaptured::default(),
+            #[cfg(feature = "file")]
+            filepath: None,
+            #[cfg(feature = "file")]
+            index_checkpoint_interval: None,
+            #[cfg(feature = "file")]
+            stream_buffer_size: None,
+            #[cfg(feature = "file")]
+            canonicalize: false,
+            frames_to_capture: None,
+            duration_to_capture: None,
+            window_to_capture: None,
+            capture_while_unfocused: true,
+            end_on_focus_loss: false,
+            preserve_existing: true,
+            edge_only: false,
+            idle_marker_interval: None,
+            use_sim_tick: false,
+            capture_accumulated_mouse: false,
+            rumble_quantization: None,
+            #[cfg(feature = "picking")]
+            capture_pointer_events: false,
+            precise_timestamps: false,
+            capture_modifiers: false,
+        }
+    }
 }
 
 impl BeginInputCapture {
+    /// Sets the [`filepath`](Self::filepath) at which to serialize captured input data, from a `&str`, [`String`] or [`PathBuf`].
+    #[cfg(feature = "file")]
+    pub fn with_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.filepath = Some(path.into());
+        self
+    }
+
+    /// When input capture will end on its own, according to [`frames_to_capture`](Self::frames_to_capture) and [`duration_to_capture`](Self::duration_to_capture).
+    pub fn stop_condition(&self) -> CaptureStopCondition {
+        match (self.frames_to_capture, self.duration_to_capture) {
+            (None, None) => CaptureStopCondition::Manual,
+            (Some(frames), None) => CaptureStopCondition::Frames(frames),
+            (None, Some(duration)) => CaptureStopCondition::Duration(duration),
+            (Some(frames), Some(duration)) => CaptureStopCondition::FirstOf(frames, duration),
+        }
+    }
+
     /// An `ObserverSystem` for `BeginInputCapture` that attaches all capture-related resources.
-    pub fn observer(trigger: Trigger<Self>, mut commands: Commands, frame_count: Res<FrameCount>) {
+    pub fn observer(
+        trigger: Trigger<Self>,
+        mut commands: Commands,
+        (frame_count, sim_tick): (Res<FrameCount>, Option<Res<SimTick>>),
+        time: Res<Time>,
+        mut capture_state: ResMut<CaptureState>,
+        windows: Query<&Window>,
+        primary_window: Query<Entity, With<PrimaryWindow>>,
+    ) {
         let event = trigger.event();
-        commands.init_resource::<TimestampedInputs>();
+        *capture_state = CaptureState::Capturing;
+        let frame = match (event.use_sim_tick, sim_tick.as_deref()) {
+            (true, Some(sim_tick)) => sim_tick.frame(),
+            _ => *frame_count,
+        };
+        if event.preserve_existing {
+            commands.init_resource::<TimestampedInputs>();
+        } else {
+            commands.insert_resource(TimestampedInputs::default());
+        }
         commands.insert_resource(event.input_modes_captured.clone());
+        #[cfg(feature = "file")]
         if let Some(path) = &event.filepath {
-            commands.insert_resource(PlaybackFilePath::new(path));
+            commands.insert_resource(PlaybackFilePath::new(path.clone()));
+        }
+        #[cfg(feature = "file")]
+        if let Some(interval) = event.index_checkpoint_interval {
+            commands.insert_resource(IndexCheckpointInterval(interval));
+        }
+        #[cfg(feature = "file")]
+        if let Some(buffer_size) = event.stream_buffer_size {
+            commands.insert_resource(StreamingCapture::new(buffer_size));
+        }
+        #[cfg(feature = "file")]
+        if event.canonicalize {
+            commands.init_resource::<CanonicalizeRecording>();
         }
         if let Some(final_frame) = event.frames_to_capture {
             commands.insert_resource(FinalCaptureFrame(FrameCount(
                 frame_count.0.wrapping_add(final_frame.0),
             )));
         }
+        if let Some(duration) = event.duration_to_capture {
+            commands.insert_resource(FinalCaptureTime(time.elapsed() + duration));
+        }
+        if let CaptureStopCondition::FirstOf(frames, duration) = event.stop_condition() {
+            warn!(
+                "Both frames_to_capture ({}) and duration_to_capture ({duration:?}) are set: input capture ends at whichever is reached first",
+                frames.0
+            );
+        }
         if let Some(window_entity) = &event.window_to_capture {
             commands.insert_resource(InputCaptureWindow(*window_entity));
         }
+        if !event.capture_while_unfocused {
+            commands.init_resource::<CaptureOnlyWhenFocused>();
+        }
+        if event.end_on_focus_loss {
+            commands.init_resource::<EndCaptureOnFocusLoss>();
+        }
+        if event.edge_only {
+            commands.init_resource::<CaptureEdgesOnly>();
+        }
+        if let Some(interval) = event.idle_marker_interval {
+            commands.insert_resource(IdleMarkers {
+                interval,
+                since: frame,
+            });
+        }
+        if event.capture_accumulated_mouse {
+            commands.init_resource::<CaptureAccumulatedMouse>();
+        }
+        if event.use_sim_tick {
+            commands.init_resource::<CaptureSimTicks>();
+        }
+        if let Some(step) = event.rumble_quantization {
+            commands.insert_resource(RumbleQuantization::new(step));
+        }
+        if event.precise_timestamps {
+            commands.init_resource::<PreciseTimestamps>();
+        }
+        if event.capture_modifiers {
+            commands.init_resource::<CaptureModifiers>();
+        }
+        #[cfg(feature = "picking")]
+        if event.capture_pointer_events {
+            commands.init_resource::<crate::picking::CapturePointerEvents>();
+        }
+
+        let window = event
+            .window_to_capture
+            .or_else(|| primary_window.get_single().ok());
+
+        // Record the size of the captured window, which cursor positions are relative to
+        if let Some(resolution) = window
+            .and_then(|window| windows.get(window).ok())
+            .map(|window| window.resolution.size())
+        {
+            commands.queue(move |world: &mut World| {
+                world
+                    .resource_mut::<TimestampedInputs>()
+                    .header
+                    .recorded_resolution = Some(resolution);
+            });
+        }
+
+        // Snapshot the cursor position, as it may have moved before capture began
+        if event.input_modes_captured.mouse_motion {
+            let position = window
+                .and_then(|window| windows.get(window).ok())
+                .and_then(Window::cursor_position);
+
+            if let (Some(window), Some(position)) = (window, position) {
+                let cursor_moved = CursorMoved {
+                    window,
+                    position,
+                    delta: None,
+                };
+                let time_since_startup = time.elapsed();
+                commands.queue(move |world: &mut World| {
+                    let input_event = InputEvent::from(cursor_moved);
+                    // The snapshot is filtered just like any other captured event
+                    let passes_filter = world
+                        .get_resource::<CaptureFilter>()
+                        .map(|capture_filter| (capture_filter.0)(&input_event))
+                        .unwrap_or(true);
+                    if passes_filter {
+                        world.resource_mut::<TimestampedInputs>().send(
+                            frame,
+                            time_since_startup,
+                            input_event,
+                        );
+                    }
+                });
+            }
+        }
     }
 }
 
@@ -85,22 +405,259 @@ pub struct EndInputCapture;
 
 impl EndInputCapture {
     /// An `ObserverSystem` for `EndInputCapture` that removes all capture-related resources and serializes timestamps if `PlaybackFilePath` exists.
+    #[allow(clippy::too_many_arguments)]
     pub fn observer(
         _trigger: Trigger<Self>,
         mut commands: Commands,
-        captured_inputs: Res<TimestampedInputs>,
-        playback_file: Option<Res<PlaybackFilePath>>,
+        mut capture_state: ResMut<CaptureState>,
+        #[cfg(feature = "file")] mut captured_inputs: ResMut<TimestampedInputs>,
+        #[cfg(feature = "file")] playback_file: Option<Res<PlaybackFilePath>>,
+        #[cfg(feature = "file")] checkpoint_interval: Option<Res<IndexCheckpointInterval>>,
+        #[cfg(feature = "file")] streaming_capture: Option<ResMut<StreamingCapture>>,
+        #[cfg(feature = "file")] canonicalize: Option<Res<CanonicalizeRecording>>,
     ) {
+        *capture_state = CaptureState::Idle;
+
         // if a PlaybackFilePath exists, serialize `TimestampedInputs` and remove it
+        #[cfg(feature = "file")]
         if let Some(playback_file) = playback_file.as_deref() {
-            serialize_timestamped_inputs(&captured_inputs, playback_file);
+            if canonicalize.is_some() && streaming_capture.is_none() {
+                captured_inputs.canonicalize();
+            }
+            match (
+                playback_file.path(),
+                checkpoint_interval.as_deref(),
+                streaming_capture,
+            ) {
+                (Some(path), _, Some(mut streaming_capture)) => {
+                    streaming_capture
+                        .flush(&captured_inputs, path, true)
+                        .expect("Could not stream captured input to file.");
+                }
+                (Some(path), Some(interval), None) => {
+                    write_seekable_recording(&captured_inputs, path, interval.0)
+                        .expect("Could not write seekable recording to file.");
+                }
+                _ => serialize_timestamped_inputs(&captured_inputs, playback_file),
+            }
             commands.remove_resource::<TimestampedInputs>();
             commands.remove_resource::<PlaybackFilePath>();
         }
         // also remove capture-related resources
         commands.remove_resource::<InputModesCaptured>();
         commands.remove_resource::<FinalCaptureFrame>();
+        commands.remove_resource::<FinalCaptureTime>();
         commands.remove_resource::<InputCaptureWindow>();
+        commands.remove_resource::<CaptureOnlyWhenFocused>();
+        commands.remove_resource::<EndCaptureOnFocusLoss>();
+        commands.remove_resource::<CaptureEdgesOnly>();
+        commands.remove_resource::<IdleMarkers>();
+        commands.remove_resource::<CaptureAccumulatedMouse>();
+        commands.remove_resource::<RumbleQuantization>();
+        commands.remove_resource::<CaptureSimTicks>();
+        commands.remove_resource::<PreciseTimestamps>();
+        commands.remove_resource::<CaptureModifiers>();
+        #[cfg(feature = "picking")]
+        commands.remove_resource::<crate::picking::CapturePointerEvents>();
+        #[cfg(feature = "file")]
+        commands.remove_resource::<IndexCheckpointInterval>();
+        #[cfg(feature = "file")]
+        commands.remove_resource::<StreamingCapture>();
+        #[cfg(feature = "file")]
+        commands.remove_resource::<CanonicalizeRecording>();
+    }
+}
+
+/// An Observer that users can trigger to write any buffered inputs of a streaming capture to disk, without ending input capture.
+///
+/// The file is synced to disk, so that it remains a valid recording even if the app crashes immediately afterwards.
+/// Has no effect unless capture was begun with a [`BeginInputCapture::stream_buffer_size`].
+#[cfg(feature = "file")]
+#[derive(Debug, Event)]
+pub struct FlushCapture;
+
+#[cfg(feature = "file")]
+impl FlushCapture {
+    /// An `ObserverSystem` for `FlushCapture` that writes all buffered inputs to the [`PlaybackFilePath`].
+    pub fn observer(
+        _trigger: Trigger<Self>,
+        captured_inputs: Option<Res<TimestampedInputs>>,
+        playback_file: Option<Res<PlaybackFilePath>>,
+        streaming_capture: Option<ResMut<StreamingCapture>>,
+    ) {
+        let (Some(captured_inputs), Some(playback_file), Some(mut streaming_capture)) =
+            (captured_inputs, playback_file, streaming_capture)
+        else {
+            return;
+        };
+
+        if let Some(path) = playback_file.path() {
+            streaming_capture
+                .flush(&captured_inputs, path, true)
+                .expect("Could not stream captured input to file.");
+        }
+    }
+}
+
+/// An Observer that users can trigger to temporarily stop capturing inputs, without ending input capture.
+///
+/// Inputs sent while capture is paused are not recorded.
+/// Has no effect unless the [`CaptureState`] is [`CaptureState::Capturing`].
+#[derive(Debug, Event)]
+pub struct PauseInputCapture;
+
+impl PauseInputCapture {
+    /// An `ObserverSystem` for `PauseInputCapture` that pauses any active input capture.
+    pub fn observer(_trigger: Trigger<Self>, mut capture_state: ResMut<CaptureState>) {
+        if *capture_state == CaptureState::Capturing {
+            *capture_state = CaptureState::Paused;
+        }
+    }
+}
+
+/// An Observer that users can trigger to resume input capture after a [`PauseInputCapture`].
+///
+/// Has no effect unless the [`CaptureState`] is [`CaptureState::Paused`].
+#[derive(Debug, Event)]
+pub struct ResumeInputCapture;
+
+impl ResumeInputCapture {
+    /// An `ObserverSystem` for `ResumeInputCapture` that resumes paused input capture.
+    pub fn observer(_trigger: Trigger<Self>, mut capture_state: ResMut<CaptureState>) {
+        if *capture_state == CaptureState::Paused {
+            *capture_state = CaptureState::Capturing;
+        }
+    }
+}
+
+/// Pauses input capture whenever the app leaves the provided `state`, and resumes it when the `state` is entered again.
+///
+/// This is useful to only record gameplay, and not the time spent in menus.
+/// If capture begins outside of the `state`, it starts out paused.
+/// Requires the [`InputCapturePlugin`] and the `StatesPlugin`, and the state type `S` must be initialized.
+/// Only available with the `states` feature.
+#[cfg(feature = "states")]
+#[derive(Debug)]
+pub struct CaptureInStatePlugin<S: States> {
+    /// The state in which inputs are captured.
+    pub state: S,
+}
+
+#[cfg(feature = "states")]
+impl<S: States> CaptureInStatePlugin<S> {
+    /// Creates a new [`CaptureInStatePlugin`], which only captures inputs while in the provided `state`.
+    pub fn new(state: S) -> Self {
+        CaptureInStatePlugin { state }
+    }
+}
+
+#[cfg(feature = "states")]
+impl<S: States> Plugin for CaptureInStatePlugin<S> {
+    fn build(&self, app: &mut App) {
+        let state = self.state.clone();
+        app.add_systems(OnEnter(self.state.clone()), |mut commands: Commands| {
+            commands.trigger(ResumeInputCapture);
+        })
+        .add_systems(OnExit(self.state.clone()), |mut commands: Commands| {
+            commands.trigger(PauseInputCapture);
+        })
+        .add_observer(
+            move |_trigger: Trigger<BeginInputCapture>, mut commands: Commands| {
+                let state = state.clone();
+                // Queued, so that this runs after capture has begun
+                commands.queue(move |world: &mut World| {
+                    let in_state = world
+                        .get_resource::<State<S>>()
+                        .is_some_and(|current| *current.get() == state);
+                    if !in_state {
+                        world.trigger(PauseInputCapture);
+                    }
+                });
+            },
+        );
+    }
+}
+
+/// Whether or not inputs are currently being captured.
+///
+/// This resource is maintained by the [`BeginInputCapture`], [`PauseInputCapture`], [`ResumeInputCapture`] and [`EndInputCapture`] observers,
+/// and is the single source of truth for the capture lifecycle.
+/// Systems can use `run_if(resource_equals(CaptureState::Capturing))` to only run while inputs are being captured.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CaptureState {
+    /// Input capture has not begun, or has ended.
+    #[default]
+    Idle,
+    /// Inputs are being captured.
+    Capturing,
+    /// Input capture has begun, but is temporarily paused.
+    Paused,
+}
+
+impl CaptureState {
+    /// Are inputs currently being captured?
+    pub fn is_capturing(&self) -> bool {
+        *self == CaptureState::Capturing
+    }
+}
+
+/// An Observer that users can trigger to begin capturing inputs into the recording called `name` in [`NamedRecordings`].
+///
+/// Any number of named captures can be active at once, independently of the [`BeginInputCapture`] lifecycle.
+/// If a recording with this name already exists, new inputs are appended to it.
+#[derive(Debug, Event)]
+pub struct BeginNamedInputCapture {
+    /// The name of the recording to capture inputs into.
+    pub name: String,
+    /// The input mechanisms that will be captured into this recording, see [`InputModesCaptured`].
+    pub input_modes_captured: InputModesCaptured,
+}
+
+impl BeginNamedInputCapture {
+    /// An `ObserverSystem` for `BeginNamedInputCapture` that begins capturing into the named recording.
+    pub fn observer(
+        trigger: Trigger<Self>,
+        mut named_recordings: ResMut<NamedRecordings>,
+        mut active_captures: ResMut<ActiveNamedCaptures>,
+    ) {
+        let event = trigger.event();
+        named_recordings.0.entry(event.name.clone()).or_default();
+        active_captures
+            .0
+            .insert(event.name.clone(), event.input_modes_captured.clone());
+    }
+}
+
+/// An Observer that users can trigger to stop capturing inputs into the recording called `name` in [`NamedRecordings`].
+///
+/// The recording itself remains in [`NamedRecordings`].
+#[derive(Debug, Event)]
+pub struct EndNamedInputCapture {
+    /// The name of the recording to stop capturing inputs into.
+    pub name: String,
+}
+
+impl EndNamedInputCapture {
+    /// An `ObserverSystem` for `EndNamedInputCapture` that stops capturing into the named recording.
+    pub fn observer(trigger: Trigger<Self>, mut active_captures: ResMut<ActiveNamedCaptures>) {
+        active_captures.0.remove(&trigger.event().name);
+    }
+}
+
+/// Independent recordings, keyed by name.
+///
+/// Inputs are captured into these recordings via [`BeginNamedInputCapture`] and [`EndNamedInputCapture`].
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct NamedRecordings(pub HashMap<String, TimestampedInputs>);
+
+/// The names of the recordings in [`NamedRecordings`] that are currently capturing inputs, and which input modes they capture.
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct ActiveNamedCaptures(HashMap<String, InputModesCaptured>);
+
+impl ActiveNamedCaptures {
+    /// Is the recording called `name` currently capturing inputs?
+    pub fn is_active(&self, name: &str) -> bool {
+        self.0.contains_key(name)
     }
 }
 
@@ -110,12 +667,394 @@ impl EndInputCapture {
 #[derive(Debug, Resource)]
 pub struct FinalCaptureFrame(FrameCount);
 
+/// The time since startup at which inputs will stop being captured.
+///
+/// If this Resource is attached, [`TimestampedInputs`] will be serialized and input capture will stop once [`Time::elapsed`] reaches this value.
+#[derive(Debug, Resource)]
+pub struct FinalCaptureTime(Duration);
+
+/// When input capture will end on its own, as returned by [`BeginInputCapture::stop_condition`].
+///
+/// Capture can always be ended early with [`EndInputCapture`], or by sending [`AppExit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureStopCondition {
+    /// Capture only ends when [`EndInputCapture`] is triggered.
+    Manual,
+    /// Capture ends after this many frames.
+    Frames(FrameCount),
+    /// Capture ends after this much time has elapsed.
+    Duration(Duration),
+    /// Capture ends after this many frames, or after this much time has elapsed, whichever comes first.
+    FirstOf(FrameCount, Duration),
+}
+
 /// The `Window` entity for which inputs will be captured.
 ///
 /// If this Resource is attached, only input events on the window corresponding to this entity will be captured.
 #[derive(Debug, Resource)]
 pub struct InputCaptureWindow(Entity);
 
+/// If this Resource is attached, inputs are not captured while their window lacks focus, according to [`Window::focused`].
+///
+/// Inputs that are sent to a window are checked against that window.
+/// Other inputs, such as gamepad events, are checked against the [`InputCaptureWindow`], or else the primary window.
+/// [`AppExit`] events are always captured.
+#[derive(Debug, Default, Resource)]
+pub struct CaptureOnlyWhenFocused;
+
+/// If this Resource is attached, input capture is ended by [`end_capture_on_focus_loss`] once the captured window loses focus.
+///
+/// This is useful to only record while the app is being played.
+#[derive(Debug, Default, Resource)]
+pub struct EndCaptureOnFocusLoss;
+
+/// Tracks which windows have focus, in order to apply [`CaptureOnlyWhenFocused`].
+#[derive(SystemParam)]
+pub struct CaptureFocus<'w, 's> {
+    only_when_focused: Option<Res<'w, CaptureOnlyWhenFocused>>,
+    windows: Query<'w, 's, &'static Window>,
+    primary_window: Query<'w, 's, Entity, With<PrimaryWindow>>,
+}
+
+impl CaptureFocus<'_, '_> {
+    /// Should the `input_event` be captured, given the focus of its window?
+    pub fn allows(
+        &self,
+        input_event: &InputEvent,
+        window_to_capture: Option<&InputCaptureWindow>,
+    ) -> bool {
+        if self.only_when_focused.is_none() || matches!(input_event, InputEvent::AppExit) {
+            return true;
+        }
+
+        let window = event_window(input_event)
+            .or(window_to_capture.map(|window_to_capture| window_to_capture.0))
+            .or_else(|| self.primary_window.get_single().ok());
+        window
+            .and_then(|window| self.windows.get(window).ok())
+            .is_none_or(|window| window.focused)
+    }
+}
+
+/// If this Resource is attached, buttonlike inputs are only captured when their state differs from the last captured state of that button.
+///
+/// This applies to keyboard and mouse button events.
+#[derive(Debug, Default, Resource)]
+pub struct CaptureEdgesOnly {
+    last_states: HashMap<ButtonlikeInput, ButtonState>,
+}
+
+impl CaptureEdgesOnly {
+    /// Records the new state of the button changed by `input_event`, returning `true` if its state has changed.
+    ///
+    /// Events that are not buttonlike always count as changes.
+    fn is_edge(&mut self, input_event: &InputEvent) -> bool {
+        let Some((input, state)) = input_event.buttonlike_state() else {
+            return true;
+        };
+
+        self.last_states.insert(input, state) != Some(state)
+    }
+}
+
+/// If this resource exists, captured input data is streamed to the [`PlaybackFilePath`] as it is captured,
+/// with one RON-serialized event per line.
+///
+/// Buffered events are written by [`stream_captured_input`] once `buffer_size` of them have accumulated,
+/// whenever a [`FlushCapture`] is triggered, and when capture ends.
+/// Streamed recordings can be read with [`read_streamed_recording`](crate::serde::read_streamed_recording).
+#[cfg(feature = "file")]
+#[derive(Debug, Resource, Clone, PartialEq, Eq)]
+pub struct StreamingCapture {
+    /// The number of buffered events at which they are written to disk.
+    pub buffer_size: usize,
+    /// The number of events in the [`TimestampedInputs`] that have already been written.
+    written: usize,
+    /// Has the file been created (or truncated) yet?
+    created: bool,
+}
+
+#[cfg(feature = "file")]
+impl StreamingCapture {
+    /// Creates a new [`StreamingCapture`], which writes to disk once `buffer_size` events have been buffered.
+    pub fn new(buffer_size: usize) -> Self {
+        StreamingCapture {
+            buffer_size,
+            written: 0,
+            created: false,
+        }
+    }
+
+    /// The number of captured events that have not yet been written to disk.
+    pub fn buffered(&self, captured_inputs: &TimestampedInputs) -> usize {
+        captured_inputs.len().saturating_sub(self.written)
+    }
+
+    /// Appends any unwritten events in `captured_inputs` to the recording at `path`.
+    ///
+    /// If `sync` is `true`, the file is synced to disk as well.
+    fn flush(
+        &mut self,
+        captured_inputs: &TimestampedInputs,
+        path: &std::path::Path,
+        sync: bool,
+    ) -> std::io::Result<()> {
+        let unwritten = captured_inputs
+            .events
+            .get(self.written..)
+            .unwrap_or_default();
+        if unwritten.is_empty() && self.created && !sync {
+            return Ok(());
+        }
+
+        append_to_streamed_recording(unwritten, path, !self.created, sync)?;
+        self.written = captured_inputs.len();
+        self.created = true;
+        Ok(())
+    }
+}
+
+/// Streams captured input to disk once enough events have been buffered, see [`StreamingCapture`].
+#[cfg(feature = "file")]
+pub fn stream_captured_input(
+    captured_inputs: Res<TimestampedInputs>,
+    playback_file: Res<PlaybackFilePath>,
+    mut streaming_capture: ResMut<StreamingCapture>,
+) {
+    if streaming_capture.buffered(&captured_inputs) < streaming_capture.buffer_size {
+        return;
+    }
+
+    if let Some(path) = playback_file.path() {
+        streaming_capture
+            .flush(&captured_inputs, path, false)
+            .expect("Could not stream captured input to file.");
+    }
+}
+
+/// If this resource exists, captured input data is serialized as a seekable recording,
+/// with a checkpoint in its `.idx` sidecar file every this many events.
+///
+/// See [`write_seekable_recording`].
+#[cfg(feature = "file")]
+#[derive(Debug, Resource, Clone, Copy, PartialEq, Eq)]
+pub struct IndexCheckpointInterval(pub usize);
+
+/// If this resource exists, captured input data is canonicalized before it is serialized when capture ends.
+///
+/// See [`TimestampedInputs::canonicalize`].
+/// Streamed captures are written as they are recorded, and so are never canonicalized.
+#[cfg(feature = "file")]
+#[derive(Debug, Resource, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CanonicalizeRecording;
+
+/// Key-value pairs that are saved in the [`RecordingHeader::metadata`](crate::timestamped_input::RecordingHeader::metadata) of the recording being captured.
+///
+/// This can be used to tag recordings with the build, platform or test that produced them.
+/// The metadata is copied into the [`TimestampedInputs`] by [`record_metadata`] while capturing, and has no effect on playback.
+#[derive(Debug, Resource, Clone, Default, PartialEq, Eq)]
+pub struct RecordingMetadata(pub HashMap<String, String>);
+
+/// Copies the [`RecordingMetadata`] into the header of the [`TimestampedInputs`] being captured.
+pub fn record_metadata(
+    metadata: Res<RecordingMetadata>,
+    mut timestamped_input: ResMut<TimestampedInputs>,
+    capture_state: Res<CaptureState>,
+) {
+    if capture_state.is_capturing() && timestamped_input.header.metadata != metadata.0 {
+        timestamped_input.header.metadata = metadata.0.clone();
+    }
+}
+
+/// If this resource exists, only input events for which the stored predicate returns `true` are captured by [`capture_input`].
+///
+/// This is checked before any other filtering, such as [`CaptureEdgesOnly`].
+#[derive(Resource)]
+pub struct CaptureFilter(pub Box<dyn Fn(&InputEvent) -> bool + Send + Sync>);
+
+impl CaptureFilter {
+    /// Creates a new [`CaptureFilter`] from the provided `predicate`.
+    pub fn new(predicate: impl Fn(&InputEvent) -> bool + Send + Sync + 'static) -> Self {
+        CaptureFilter(Box::new(predicate))
+    }
+}
+
+/// If this resource exists, captured inputs are timestamped with the current [`SimTick`] rather than the [`FrameCount`].
+///
+/// The tick is stored in the `frame` field of each captured event.
+/// If there is no [`SimTick`] resource, the [`FrameCount`] is used instead.
+#[derive(Debug, Resource, Default)]
+pub struct CaptureSimTicks;
+
+/// The clock against which captured inputs are timestamped: either the [`FrameCount`], or the [`SimTick`].
+///
+/// See [`CaptureSimTicks`].
+#[derive(SystemParam)]
+pub struct CaptureClock<'w> {
+    frame_count: Res<'w, FrameCount>,
+    sim_tick: Option<Res<'w, SimTick>>,
+    capture_sim_ticks: Option<Res<'w, CaptureSimTicks>>,
+}
+
+impl CaptureClock<'_> {
+    /// The frame under which inputs captured now are stored.
+    pub fn frame(&self) -> FrameCount {
+        match (self.capture_sim_ticks.as_ref(), self.sim_tick.as_deref()) {
+            (Some(_), Some(sim_tick)) => sim_tick.frame(),
+            _ => *self.frame_count,
+        }
+    }
+}
+
+/// If this resource exists, snapshots of the [`AccumulatedMouseMotion`] and [`AccumulatedMouseScroll`] resources
+/// are captured by [`capture_accumulated_mouse`] each frame.
+///
+/// Only frames where the accumulated value is non-zero are recorded,
+/// as Bevy resets these resources to zero on every frame without mouse input.
+#[derive(Debug, Resource, Default)]
+pub struct CaptureAccumulatedMouse;
+
+/// If this resource exists, captured [`InputEvent::GamepadRumble`] requests are thinned out by [`capture_input`].
+///
+/// The intensities of each [`RumbleRequest::Add`] are rounded to the nearest multiple of `step`,
+/// and requests whose rounded intensities and duration match the last captured request for the same gamepad are dropped.
+/// This keeps recordings of smooth rumble curves compact, at the cost of some precision.
+#[derive(Debug, Resource, Clone, PartialEq, Default)]
+pub struct RumbleQuantization {
+    /// The intensity step to which rumble intensities are rounded.
+    ///
+    /// Steps that are not positive disable rounding, but repeated intensities are still dropped.
+    pub step: f32,
+    /// The most recently captured (strong, weak) motor intensities and duration for each gamepad.
+    last_requests: HashMap<Entity, (f32, f32, Duration)>,
+}
+
+impl RumbleQuantization {
+    /// Creates a new [`RumbleQuantization`] with the provided intensity `step`.
+    pub fn new(step: f32) -> Self {
+        RumbleQuantization {
+            step,
+            last_requests: HashMap::default(),
+        }
+    }
+
+    /// Quantizes the provided `input_event`, returning [`None`] if it should be dropped.
+    ///
+    /// Events that are not rumble requests are returned unchanged.
+    fn quantize(&mut self, input_event: InputEvent) -> Option<InputEvent> {
+        let InputEvent::GamepadRumble(request) = input_event else {
+            return Some(input_event);
+        };
+
+        match request {
+            RumbleRequest::Add {
+                duration,
+                strong_motor,
+                weak_motor,
+                gamepad,
+            } => {
+                let round = |intensity: f32| {
+                    if self.step > 0.0 {
+                        (intensity / self.step).round() * self.step
+                    } else {
+                        intensity
+                    }
+                };
+                let quantized = (round(strong_motor), round(weak_motor), duration);
+                if self.last_requests.insert(gamepad, quantized) == Some(quantized) {
+                    return None;
+                }
+
+                Some(InputEvent::GamepadRumble(RumbleRequest::Add {
+                    duration,
+                    strong_motor: quantized.0,
+                    weak_motor: quantized.1,
+                    gamepad,
+                }))
+            }
+            RumbleRequest::Stop { gamepad } => {
+                self.last_requests.remove(&gamepad);
+                Some(input_event)
+            }
+        }
+    }
+}
+
+/// If this resource exists, each captured event is also stamped with a [`TimestampedInputEvent::precise_time`].
+///
+/// This applies to events recorded by [`capture_input`], [`capture_accumulated_mouse`] and, with the `picking` feature,
+/// `capture_pointer_events`.
+///
+/// `time_since_startup` is only sampled once per frame, so all events captured in the same frame share it.
+/// Precise timestamps instead read the system clock each time a capture system records a batch of events, measured from when capture began.
+/// Bevy delivers input events to the capture systems in per-frame batches,
+/// so this is the time at which each batch was captured, not when the OS reported each event:
+/// events that share a precise timestamp were captured in the same batch, in the order they are stored.
+/// Precise timestamps never decrease.
+#[derive(Debug, Resource, Clone, Copy)]
+pub struct PreciseTimestamps {
+    /// When capture began.
+    started: Instant,
+}
+
+impl Default for PreciseTimestamps {
+    fn default() -> Self {
+        PreciseTimestamps {
+            started: Instant::now(),
+        }
+    }
+}
+
+impl PreciseTimestamps {
+    /// Stamps each of the provided `events`, which were captured as one batch, with the current precise time.
+    pub(crate) fn stamp(&self, events: &mut [TimestampedInputEvent]) {
+        let precise_time = self.started.elapsed();
+        for event in events {
+            event.precise_time = Some(precise_time);
+        }
+    }
+}
+
+/// If this resource exists, keyboard and mouse events recorded by [`capture_input`] are annotated with [`TimestampedInputEvent::modifiers`].
+///
+/// The modifier keys are read from the [`ButtonInput<KeyCode>`] resource, which reflects all keyboard events of the current frame,
+/// so modifiers pressed in the same frame as an event are reported as held.
+/// This is metadata for analysis, such as debugging chord detection, and is not played back.
+#[derive(Debug, Resource, Clone, Copy, Default)]
+pub struct CaptureModifiers;
+
+/// If this resource exists, [`InputEvent::IdleMarker`] events are captured whenever no inputs have been captured for `interval` frames.
+///
+/// These markers act as timing anchors during [`PlaybackStrategy::FrameCount`](crate::input_playback::PlaybackStrategy::FrameCount) playback,
+/// so that long idle periods take at least as long to replay as they did to record, see [`IdleMarkerDelay`](crate::input_playback::IdleMarkerDelay).
+#[derive(Debug, Resource)]
+pub struct IdleMarkers {
+    /// The number of idle frames after which a marker is recorded.
+    pub interval: u64,
+    /// The frame at which input capture began.
+    since: FrameCount,
+}
+
+impl IdleMarkers {
+    /// Records an [`InputEvent::IdleMarker`] if nothing has been captured in the last `interval` frames.
+    fn record_if_idle(
+        &self,
+        timestamped_input: &mut TimestampedInputs,
+        frame: FrameCount,
+        time_since_startup: Duration,
+    ) {
+        let last_frame = timestamped_input
+            .events
+            .last()
+            .map(|event| event.frame.0.max(self.since.0))
+            .unwrap_or(self.since.0);
+
+        if u64::from(frame.0.wrapping_sub(last_frame)) >= self.interval {
+            timestamped_input.send(frame, time_since_startup, InputEvent::IdleMarker);
+        }
+    }
+}
+
 /// The input mechanisms captured via the [`InputCapturePlugin`], configured as a resource.
 ///
 /// By default, all supported input modes will be captured.
@@ -133,6 +1072,10 @@ pub struct InputModesCaptured {
     ///
     /// Captures gamepad connections, button presses and axis values
     pub gamepad: bool,
+    /// Window events
+    ///
+    /// Captures the window being moved
+    pub window: bool,
 }
 
 impl InputModesCaptured {
@@ -142,6 +1085,7 @@ impl InputModesCaptured {
         mouse_motion: false,
         keyboard: false,
         gamepad: false,
+        window: false,
     };
 
     /// Captures all supported input modes
@@ -150,9 +1094,29 @@ impl InputModesCaptured {
         mouse_motion: true,
         keyboard: true,
         gamepad: true,
+        window: true,
     };
 }
 
+impl InputModesCaptured {
+    /// Is the provided `input_event` captured under these input modes?
+    ///
+    /// [`InputEvent::AppExit`] events are always captured.
+    pub fn captures(&self, input_event: &InputEvent) -> bool {
+        match input_event {
+            InputEvent::Keyboard(_) | InputEvent::Ime(_) => self.keyboard,
+            InputEvent::MouseButton(_) | InputEvent::MouseWheel(_) => self.mouse_buttons,
+            InputEvent::Pointer(_) => self.mouse_buttons,
+            InputEvent::CursorMoved(_) => self.mouse_motion,
+            InputEvent::Gamepad(_) | InputEvent::GamepadRumble(_) => self.gamepad,
+            InputEvent::AccumulatedMouseMotion(_) => self.mouse_motion,
+            InputEvent::AccumulatedMouseScroll(_) => self.mouse_buttons,
+            InputEvent::WindowMoved(_) => self.window,
+            InputEvent::AppExit | InputEvent::IdleMarker => true,
+        }
+    }
+}
+
 impl Default for InputModesCaptured {
     fn default() -> Self {
         InputModesCaptured::ENABLE_ALL
@@ -162,32 +1126,66 @@ impl Default for InputModesCaptured {
 /// Captures input from the [`bevy::window`] and [`bevy::input`] event streams.
 ///
 /// The input modes can be controlled via the [`InputModesCaptured`] resource.
+/// Inputs are only captured while the [`CaptureState`] is [`CaptureState::Capturing`].
 #[allow(clippy::too_many_arguments)]
 pub fn capture_input(
     mut mouse_button_events: EventReader<MouseButtonInput>,
     mut mouse_wheel_events: EventReader<MouseWheel>,
-    mut cursor_moved_events: EventReader<CursorMoved>,
-    mut keyboard_events: EventReader<KeyboardInput>,
+    (mut cursor_moved_events, mut window_moved_events): (
+        EventReader<CursorMoved>,
+        EventReader<WindowMoved>,
+    ),
+    (mut keyboard_events, mut ime_events): (EventReader<KeyboardInput>, EventReader<Ime>),
     mut gamepad_events: EventReader<GamepadEvent>,
+    (mut gamepad_rumble_requests, mut rumble_quantization): (
+        EventReader<GamepadRumbleRequest>,
+        Option<ResMut<RumbleQuantization>>,
+    ),
     mut app_exit_events: EventReader<AppExit>,
     mut timestamped_input: ResMut<TimestampedInputs>,
-    window_to_capture: Option<Res<InputCaptureWindow>>,
+    (window_to_capture, capture_focus): (Option<Res<InputCaptureWindow>>, CaptureFocus),
     input_modes_captured: Option<Res<InputModesCaptured>>,
-    frame_count: Res<FrameCount>,
-    time: Res<Time>,
+    mut edges_only: Option<ResMut<CaptureEdgesOnly>>,
+    idle_markers: Option<Res<IdleMarkers>>,
+    capture_filter: Option<Res<CaptureFilter>>,
+    capture_state: Res<CaptureState>,
+    capture_clock: CaptureClock,
+    (time, precise_timestamps, capture_modifiers, key_input): (
+        Res<Time>,
+        Option<Res<PreciseTimestamps>>,
+        Option<Res<CaptureModifiers>>,
+        Option<Res<ButtonInput<KeyCode>>>,
+    ),
 ) {
-    let Some(input_modes_captured) = input_modes_captured else {
+    let Some(input_modes_captured) = input_modes_captured.filter(|_| capture_state.is_capturing())
+    else {
         mouse_button_events.clear();
         mouse_wheel_events.clear();
         cursor_moved_events.clear();
         keyboard_events.clear();
+        ime_events.clear();
         gamepad_events.clear();
+        gamepad_rumble_requests.clear();
+        window_moved_events.clear();
         app_exit_events.clear();
         return;
     };
 
     let time_since_startup = time.elapsed();
-    let frame = *frame_count;
+    let frame = capture_clock.frame();
+    let first_new_event = timestamped_input.len();
+    let passes_filter = |input_event: &InputEvent| {
+        passes_capture_filters(
+            input_event,
+            window_to_capture.as_deref(),
+            capture_filter.as_deref(),
+            &capture_focus,
+        )
+    };
+    let mut is_edge = |input_event: &InputEvent| match edges_only.as_deref_mut() {
+        Some(edges_only) => edges_only.is_edge(input_event),
+        None => true,
+    };
 
     // BLOCKED: these events are arbitrarily ordered within a frame,
     // but we have no way to access their order from winit.
@@ -199,13 +1197,10 @@ pub fn capture_input(
             time_since_startup,
             mouse_button_events
                 .read()
-                .filter(|event| {
-                    window_to_capture
-                        .as_deref()
-                        .map(|window| window.0 == event.window)
-                        .unwrap_or(true)
-                })
-                .cloned(),
+                .cloned()
+                .map(InputEvent::from)
+                .filter(|event| passes_filter(event))
+                .filter(|event| is_edge(event)),
         );
 
         timestamped_input.send_multiple(
@@ -213,13 +1208,9 @@ pub fn capture_input(
             time_since_startup,
             mouse_wheel_events
                 .read()
-                .filter(|event| {
-                    window_to_capture
-                        .as_deref()
-                        .map(|window| window.0 == event.window)
-                        .unwrap_or(true)
-                })
-                .cloned(),
+                .cloned()
+                .map(InputEvent::from)
+                .filter(|event| passes_filter(event)),
         );
     } else {
         mouse_button_events.clear();
@@ -232,13 +1223,9 @@ pub fn capture_input(
             time_since_startup,
             cursor_moved_events
                 .read()
-                .filter(|event| {
-                    window_to_capture
-                        .as_deref()
-                        .map(|window| window.0 == event.window)
-                        .unwrap_or(true)
-                })
-                .cloned(),
+                .cloned()
+                .map(InputEvent::from)
+                .filter(|event| passes_filter(event)),
         );
     } else {
         cursor_moved_events.clear();
@@ -250,28 +1237,265 @@ pub fn capture_input(
             time_since_startup,
             keyboard_events
                 .read()
-                .filter(|event| {
-                    window_to_capture
-                        .as_deref()
-                        .map(|window| window.0 == event.window)
-                        .unwrap_or(true)
-                })
-                .cloned(),
+                .cloned()
+                .map(InputEvent::from)
+                .filter(|event| passes_filter(event))
+                .filter(|event| is_edge(event)),
+        );
+
+        // Text pasted or committed by an input method arrives as a single event, which keeps its characters in order
+        timestamped_input.send_multiple(
+            frame,
+            time_since_startup,
+            ime_events
+                .read()
+                .cloned()
+                .map(InputEvent::from)
+                .filter(|event| passes_filter(event)),
         );
     } else {
-        keyboard_events.clear()
+        keyboard_events.clear();
+        ime_events.clear();
     }
 
     if input_modes_captured.gamepad {
-        timestamped_input.send_multiple(frame, time_since_startup, gamepad_events.read().cloned());
+        timestamped_input.send_multiple(
+            frame,
+            time_since_startup,
+            gamepad_events
+                .read()
+                .cloned()
+                .map(InputEvent::from)
+                .filter(|event| passes_filter(event)),
+        );
+
+        timestamped_input.send_multiple(
+            frame,
+            time_since_startup,
+            gamepad_rumble_requests
+                .read()
+                .cloned()
+                .map(InputEvent::from)
+                .filter(|event| passes_filter(event))
+                .filter_map(|event| match rumble_quantization.as_deref_mut() {
+                    Some(rumble_quantization) => rumble_quantization.quantize(event),
+                    None => Some(event),
+                }),
+        );
+    } else {
+        gamepad_events.clear();
+        gamepad_rumble_requests.clear();
+    }
+
+    if input_modes_captured.window {
+        timestamped_input.send_multiple(
+            frame,
+            time_since_startup,
+            window_moved_events
+                .read()
+                .cloned()
+                .map(InputEvent::from)
+                .filter(|event| passes_filter(event)),
+        );
     } else {
-        gamepad_events.clear()
+        window_moved_events.clear();
+    }
+
+    timestamped_input.send_multiple(
+        frame,
+        time_since_startup,
+        app_exit_events
+            .read()
+            .cloned()
+            .map(InputEvent::from)
+            .filter(|event| passes_filter(event)),
+    );
+
+    if let Some(idle_markers) = idle_markers.as_deref() {
+        idle_markers.record_if_idle(&mut timestamped_input, frame, time_since_startup);
+    }
+
+    if let Some(precise_timestamps) = precise_timestamps.as_deref() {
+        precise_timestamps.stamp(&mut timestamped_input.events[first_new_event..]);
+    }
+
+    if let (Some(_), Some(key_input)) = (capture_modifiers, key_input) {
+        let modifiers = Modifiers::from_keys(&key_input);
+        for event in &mut timestamped_input.events[first_new_event..] {
+            let device = InputEventKind::from(&event.input_event).device();
+            if matches!(device, Some(InputDevice::Keyboard | InputDevice::Mouse)) {
+                event.modifiers = Some(modifiers);
+            }
+        }
     }
+}
+
+/// Does the `input_event` pass the [`InputCaptureWindow`], the [`CaptureFilter`] and any [`CaptureOnlyWhenFocused`]?
+///
+/// Shared by [`capture_input`] and [`capture_named_input`], so that named recordings contain the same inputs as the global recording.
+fn passes_capture_filters(
+    input_event: &InputEvent,
+    window_to_capture: Option<&InputCaptureWindow>,
+    capture_filter: Option<&CaptureFilter>,
+    capture_focus: &CaptureFocus,
+) -> bool {
+    let in_captured_window = match (window_to_capture, event_window(input_event)) {
+        (Some(window_to_capture), Some(window)) => window_to_capture.0 == window,
+        _ => true,
+    };
 
-    timestamped_input.send_multiple(frame, time_since_startup, app_exit_events.read().cloned())
+    in_captured_window
+        && capture_focus.allows(input_event, window_to_capture)
+        && capture_filter
+            .map(|capture_filter| (capture_filter.0)(input_event))
+            .unwrap_or(true)
 }
 
-/// Serializes captured input to the path given in the [`PlaybackFilePath`] resource once [`AppExit`] is sent.
+/// The window that received the `input_event`, if it is tied to a window.
+fn event_window(input_event: &InputEvent) -> Option<Entity> {
+    match input_event {
+        InputEvent::Keyboard(event) => Some(event.window),
+        InputEvent::MouseButton(event) => Some(event.window),
+        InputEvent::MouseWheel(event) => Some(event.window),
+        InputEvent::CursorMoved(event) => Some(event.window),
+        InputEvent::WindowMoved(event) => Some(event.window),
+        InputEvent::Ime(
+            Ime::Preedit { window, .. }
+            | Ime::Commit { window, .. }
+            | Ime::Enabled { window }
+            | Ime::Disabled { window },
+        ) => Some(*window),
+        _ => None,
+    }
+}
+
+/// Captures snapshots of the [`AccumulatedMouseMotion`] and [`AccumulatedMouseScroll`] resources, see [`CaptureAccumulatedMouse`].
+///
+/// Like [`capture_input`], this respects the [`InputModesCaptured`] and any [`CaptureFilter`].
+#[allow(clippy::too_many_arguments)]
+pub fn capture_accumulated_mouse(
+    mut timestamped_input: ResMut<TimestampedInputs>,
+    accumulated_mouse_motion: Res<AccumulatedMouseMotion>,
+    accumulated_mouse_scroll: Res<AccumulatedMouseScroll>,
+    input_modes_captured: Option<Res<InputModesCaptured>>,
+    capture_filter: Option<Res<CaptureFilter>>,
+    capture_state: Res<CaptureState>,
+    capture_clock: CaptureClock,
+    time: Res<Time>,
+    precise_timestamps: Option<Res<PreciseTimestamps>>,
+) {
+    let Some(input_modes_captured) = input_modes_captured.filter(|_| capture_state.is_capturing())
+    else {
+        return;
+    };
+
+    let mut snapshots = Vec::new();
+    if accumulated_mouse_motion.delta != Vec2::ZERO {
+        snapshots.push(InputEvent::from(*accumulated_mouse_motion));
+    }
+    if accumulated_mouse_scroll.delta != Vec2::ZERO {
+        snapshots.push(InputEvent::from(*accumulated_mouse_scroll));
+    }
+
+    let first_new_event = timestamped_input.len();
+    timestamped_input.send_multiple(
+        capture_clock.frame(),
+        time.elapsed(),
+        snapshots.into_iter().filter(|event| {
+            input_modes_captured.captures(event)
+                && capture_filter
+                    .as_deref()
+                    .map(|capture_filter| (capture_filter.0)(event))
+                    .unwrap_or(true)
+        }),
+    );
+
+    if let Some(precise_timestamps) = precise_timestamps {
+        precise_timestamps.stamp(&mut timestamped_input.events[first_new_event..]);
+    }
+}
+
+/// Captures input from the [`bevy::window`] and [`bevy::input`] event streams into each of the [`ActiveNamedCaptures`].
+///
+/// Each named recording only captures the input modes it was begun with.
+/// Like [`capture_input`], this respects the [`InputCaptureWindow`], any [`CaptureFilter`], any [`CaptureOnlyWhenFocused`] and the [`CaptureClock`],
+/// and captures nothing while the global capture is [`CaptureState::Paused`].
+/// [`CaptureEdgesOnly`] and [`RumbleQuantization`] track the global recording, and are not applied to named recordings.
+#[allow(clippy::too_many_arguments)]
+pub fn capture_named_input(
+    mut mouse_button_events: EventReader<MouseButtonInput>,
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    (mut cursor_moved_events, mut window_moved_events): (
+        EventReader<CursorMoved>,
+        EventReader<WindowMoved>,
+    ),
+    (mut keyboard_events, mut ime_events): (EventReader<KeyboardInput>, EventReader<Ime>),
+    mut gamepad_events: EventReader<GamepadEvent>,
+    mut gamepad_rumble_requests: EventReader<GamepadRumbleRequest>,
+    mut app_exit_events: EventReader<AppExit>,
+    mut named_recordings: ResMut<NamedRecordings>,
+    active_captures: Res<ActiveNamedCaptures>,
+    (window_to_capture, capture_focus): (Option<Res<InputCaptureWindow>>, CaptureFocus),
+    capture_filter: Option<Res<CaptureFilter>>,
+    capture_state: Res<CaptureState>,
+    capture_clock: CaptureClock,
+    time: Res<Time>,
+) {
+    if active_captures.0.is_empty() || *capture_state == CaptureState::Paused {
+        mouse_button_events.clear();
+        mouse_wheel_events.clear();
+        cursor_moved_events.clear();
+        keyboard_events.clear();
+        ime_events.clear();
+        gamepad_events.clear();
+        gamepad_rumble_requests.clear();
+        window_moved_events.clear();
+        app_exit_events.clear();
+        return;
+    }
+
+    let input_events: Vec<InputEvent> = mouse_button_events
+        .read()
+        .cloned()
+        .map(InputEvent::from)
+        .chain(mouse_wheel_events.read().cloned().map(InputEvent::from))
+        .chain(cursor_moved_events.read().cloned().map(InputEvent::from))
+        .chain(keyboard_events.read().cloned().map(InputEvent::from))
+        .chain(ime_events.read().cloned().map(InputEvent::from))
+        .chain(gamepad_events.read().cloned().map(InputEvent::from))
+        .chain(
+            gamepad_rumble_requests
+                .read()
+                .cloned()
+                .map(InputEvent::from),
+        )
+        .chain(window_moved_events.read().cloned().map(InputEvent::from))
+        .chain(app_exit_events.read().cloned().map(InputEvent::from))
+        .filter(|event| {
+            passes_capture_filters(
+                event,
+                window_to_capture.as_deref(),
+                capture_filter.as_deref(),
+                &capture_focus,
+            )
+        })
+        .collect();
+
+    let frame = capture_clock.frame();
+    for (name, input_modes_captured) in active_captures.0.iter() {
+        let recording = named_recordings.0.entry(name.clone()).or_default();
+        recording.send_multiple(
+            frame,
+            time.elapsed(),
+            input_events
+                .iter()
+                .filter(|event| input_modes_captured.captures(event))
+                .cloned(),
+        );
+    }
+}
+
+/// Serializes captured input to the path given in the [`PlaybackFilePath`](crate::serde::PlaybackFilePath) resource once [`AppExit`] is sent.
 pub fn trigger_input_capture_on_exit(
     app_exit_events: EventReader<AppExit>,
     mut commands: Commands,
@@ -281,6 +1505,27 @@ pub fn trigger_input_capture_on_exit(
     }
 }
 
+/// Triggers `EndInputCapture` once the captured window loses focus.
+///
+/// The captured window is the [`InputCaptureWindow`] if there is one, and the primary window otherwise.
+pub fn end_capture_on_focus_loss(
+    mut commands: Commands,
+    mut focus_events: EventReader<WindowFocused>,
+    window_to_capture: Option<Res<InputCaptureWindow>>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+) {
+    let captured_window = window_to_capture
+        .map(|window| window.0)
+        .or_else(|| primary_window.get_single().ok());
+
+    if focus_events
+        .read()
+        .any(|event| !event.focused && Some(event.window) == captured_window)
+    {
+        commands.trigger(EndInputCapture);
+    }
+}
+
 /// Triggers `EndInputCapture` once the provided number of frames have elapsed.
 pub fn handle_final_capture_frame(
     mut commands: Commands,
@@ -292,7 +1537,22 @@ pub fn handle_final_capture_frame(
     }
 }
 
+/// Triggers `EndInputCapture` once the provided amount of time has elapsed.
+///
+/// If capture was already ended this frame by [`handle_final_capture_frame`], nothing happens.
+pub fn handle_final_capture_time(
+    mut commands: Commands,
+    time: Res<Time>,
+    final_time: Res<FinalCaptureTime>,
+    capture_state: Res<CaptureState>,
+) {
+    if *capture_state != CaptureState::Idle && time.elapsed() >= final_time.0 {
+        commands.trigger(EndInputCapture);
+    }
+}
+
 /// Writes the `timestamped_inputs` to the provided `path` (which should store [`Some(PathBuf)`]).
+#[cfg(feature = "file")]
 pub fn serialize_timestamped_inputs(
     timestamped_inputs: &TimestampedInputs,
     playback_file: &PlaybackFilePath,
@@ -313,3 +1573,40 @@ pub fn serialize_timestamped_inputs(
         .expect("Could not write string to file.");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::input::keyboard::{Key, KeyCode};
+
+    #[test]
+    fn precise_timestamps_share_batch_time() {
+        let precise_timestamps = PreciseTimestamps::default();
+
+        let mut timestamped_input = TimestampedInputs::default();
+        for _ in 0..3 {
+            timestamped_input.send(
+                FrameCount(1),
+                Duration::ZERO,
+                InputEvent::Keyboard(KeyboardInput {
+                    key_code: KeyCode::KeyF,
+                    logical_key: Key::Character("f".into()),
+                    state: ButtonState::Pressed,
+                    repeat: false,
+                    window: Entity::PLACEHOLDER,
+                }),
+            );
+        }
+        precise_timestamps.stamp(&mut timestamped_input.events[..2]);
+        precise_timestamps.stamp(&mut timestamped_input.events[2..]);
+
+        let precise_times: Vec<Duration> = timestamped_input
+            .events
+            .iter()
+            .map(|event| event.precise_time.unwrap())
+            .collect();
+        // Events stamped in the same batch share a timestamp, which later batches never precede
+        assert_eq!(precise_times[0], precise_times[1]);
+        assert!(precise_times[1] <= precise_times[2]);
+    }
+}
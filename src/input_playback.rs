@@ -2,45 +2,148 @@
 //!
 //! These are played back by emulating assorted Bevy input events.
 
-use bevy::app::{App, AppExit, First, Plugin};
+use bevy::app::{App, AppExit, First, FixedFirst, Plugin, PreUpdate};
 use bevy::core::FrameCount;
-use bevy::ecs::{prelude::*, system::SystemParam};
+use bevy::ecs::{
+    prelude::*,
+    system::{SystemParam, SystemState},
+};
 use bevy::input::{
-    gamepad::GamepadEvent,
-    keyboard::KeyboardInput,
-    mouse::{MouseButtonInput, MouseWheel},
+    gamepad::{Gamepad, GamepadConnectionEvent, GamepadEvent, GamepadRumbleRequest},
+    keyboard::{keyboard_input_system, KeyCode, KeyboardInput},
+    mouse::{
+        mouse_button_input_system, AccumulatedMouseMotion, AccumulatedMouseScroll, MouseButton,
+        MouseButtonInput, MouseWheel,
+    },
+    ButtonInput, ButtonState, InputSystem,
 };
 use bevy::log::warn;
 use bevy::time::Time;
-use bevy::utils::Duration;
-use bevy::window::{CursorMoved, PrimaryWindow, Window};
+use bevy::utils::{Duration, HashSet};
+use bevy::window::{CursorMoved, Ime, PrimaryWindow, Window, WindowMoved, WindowPosition};
+use core::hash::Hash;
+
+#[cfg(feature = "file")]
 use ron::de::from_reader;
+#[cfg(feature = "file")]
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, VecDeque};
+#[cfg(feature = "file")]
 use std::fs::File;
+#[cfg(feature = "file")]
+use std::hash::{DefaultHasher, Hasher};
+use std::ops::Bound;
+#[cfg(feature = "file")]
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
 
-use crate::serde::PlaybackFilePath;
-use crate::timestamped_input::{TimestampedInputEvent, TimestampedInputs};
+#[cfg(feature = "file")]
+use crate::serde::{
+    read_recording, stream_playback_events, LenientTimestampedInputs, PlaybackFilePath,
+    StreamingPlaybackSource,
+};
+#[cfg(feature = "file")]
+use crate::timestamped_input::is_compatible;
+use crate::timestamped_input::{
+    ButtonlikeInput, InputEvent, SimTick, TimestampedInputEvent, TimestampedInputs,
+};
 
 /// Reads from the [`TimestampedInputs`] event stream to determine which events to play back.
 ///
 /// Events are played back during the [`First`] schedule to accurately mimic the behavior of native `winit`-based inputs.
 /// Which events are played back are controlled via the [`PlaybackStrategy`] resource.
 ///  
-/// Input is deserialized on app startup from the path stored in the [`PlaybackFilePath`] resource, if any.
+/// Input is deserialized on app startup from the path stored in the [`PlaybackFilePath`](crate::serde::PlaybackFilePath) resource, if any.
 pub struct InputPlaybackPlugin;
 
 impl Plugin for InputPlaybackPlugin {
     fn build(&self, app: &mut App) {
-        app.add_observer(BeginInputPlayback::observer)
+        app.init_resource::<ReplayedAccumulatedMouse>()
+            .init_resource::<ImmediateButtonTransitions<KeyCode>>()
+            .init_resource::<ImmediateButtonTransitions<MouseButton>>()
+            .add_event::<PlaybackBreakpointHit>()
+            .add_observer(BeginInputPlayback::observer)
             .add_observer(EndInputPlayback::observer)
+            .add_observer(SetPlaybackWindow::observer)
             .add_systems(
                 First,
                 playback_timestamped_input
                     .run_if(
                         resource_exists::<PlaybackProgress>
-                            .and(resource_exists::<TimestampedInputs>),
+                            .and(resource_exists::<TimestampedInputs>)
+                            .and(not(resource_exists::<PlaybackInFixedTimestep>)),
                     )
                     .after(bevy::ecs::event::EventUpdates),
+            )
+            .add_systems(
+                FixedFirst,
+                playback_timestamped_input.run_if(
+                    resource_exists::<PlaybackProgress>
+                        .and(resource_exists::<TimestampedInputs>)
+                        .and(resource_exists::<PlaybackInFixedTimestep>),
+                ),
+            )
+            .add_systems(
+                First,
+                (
+                    (keyboard_input_system, mouse_button_input_system),
+                    (
+                        snapshot_button_transitions::<KeyCode>,
+                        snapshot_button_transitions::<MouseButton>,
+                    ),
+                )
+                    .chain()
+                    .run_if(resource_exists::<ImmediateInputUpdate>)
+                    .after(playback_timestamped_input),
+            )
+            .add_systems(
+                PreUpdate,
+                (
+                    restore_accumulated_mouse.run_if(
+                        resource_exists::<AccumulatedMouseMotion>
+                            .and(resource_exists::<AccumulatedMouseScroll>),
+                    ),
+                    (
+                        restore_button_transitions::<KeyCode>,
+                        restore_button_transitions::<MouseButton>,
+                    )
+                        .run_if(resource_exists::<ImmediateInputUpdate>),
+                )
+                    .after(InputSystem),
             );
+
+        #[cfg(feature = "file")]
+        app.add_observer(ReloadPlaybackFile::observer).add_systems(
+            First,
+            stream_playback_events
+                .run_if(
+                    resource_exists::<StreamingPlaybackSource>
+                        .and(resource_exists::<TimestampedInputs>),
+                )
+                .before(playback_timestamped_input)
+                .after(bevy::ecs::event::EventUpdates),
+        );
+
+        #[cfg(feature = "watch")]
+        app.add_systems(
+            First,
+            reload_changed_playback_file
+                .run_if(resource_exists::<WatchPlaybackFile>)
+                .before(playback_timestamped_input)
+                .after(bevy::ecs::event::EventUpdates),
+        );
+
+        #[cfg(feature = "file")]
+        app.add_systems(
+            First,
+            persist_playback_progress
+                .run_if(
+                    resource_exists::<PersistPlaybackProgress>
+                        .and(resource_exists::<PlaybackProgress>)
+                        .and(resource_exists::<TimestampedInputs>),
+                )
+                .after(playback_timestamped_input),
+        );
     }
 }
 
@@ -58,6 +161,58 @@ pub struct BeginInputPlayback {
     /// A entity corresponding to the [`bevy::window::Window`] which will receive input events.
     /// If unspecified, input events will target the serialized window entity, which may be fragile.
     pub playback_window: Option<PlaybackWindow>,
+    /// Which kinds of input events are retargeted to the `playback_window`.
+    ///
+    /// See [`RetargetedInputs`] for more information.
+    pub retargeted_inputs: RetargetedInputs,
+    /// If `true`, playback resumes from the [`PlaybackCheckpoint`] saved at the path in the [`PersistPlaybackProgress`] resource, if any.
+    ///
+    /// The checkpoint is ignored if it was saved while playing back a different recording.
+    /// Under [`PlaybackStrategy::Time`], a [`PlaybackTimeOffset`] is inserted so that the remaining events keep their recorded timing.
+    #[cfg(feature = "file")]
+    pub resume: bool,
+    /// If `true`, playback under [`PlaybackStrategy::FrameCount`] never outpaces the time at which events were recorded.
+    ///
+    /// See [`RespectRecordedDuration`] for more information.
+    pub respect_recorded_duration: bool,
+    /// If `true`, the recording is not checked for signs of corruption before it is played back.
+    ///
+    /// By default, a warning is logged if a recording with multiple events has a zero span,
+    /// see [`TimestampedInputs::has_zero_span`].
+    pub skip_validation: bool,
+    /// If `true`, [`PlaybackStrategy::FrameCount`] playback follows the current [`SimTick`] rather than the [`FrameCount`].
+    ///
+    /// See [`PlaybackSimTicks`].
+    pub use_sim_tick: bool,
+    /// If `true`, replayed keyboard and mouse button inputs are reflected in [`ButtonInput`](bevy::input::ButtonInput) during [`First`].
+    ///
+    /// See [`ImmediateInputUpdate`].
+    pub immediate_input_update: bool,
+    /// The coordinate origin used by the [`CursorMoved`] events in the recording.
+    ///
+    /// See [`CursorOrigin`] for more information.
+    pub cursor_origin: CursorOrigin,
+    /// If `true`, inputs are played back at the start of each fixed timestep, rather than once per frame.
+    ///
+    /// See [`PlaybackInFixedTimestep`].
+    pub fixed_timestep: bool,
+    /// The frame rate at which the recording was captured, if known.
+    ///
+    /// If set, [`PlaybackStrategy::RecordedFrameRate`] advances through the recorded frames at this rate,
+    /// rather than at the rate estimated by [`TimestampedInputs::recorded_frame_duration`]. See [`PlaybackSourceFrameRate`].
+    /// This only affects [`PlaybackStrategy::RecordedFrameRate`]: a warning is logged if it is combined with any other strategy,
+    /// or with [`respect_recorded_duration`](Self::respect_recorded_duration) or [`use_sim_tick`](Self::use_sim_tick),
+    /// which only affect [`PlaybackStrategy::FrameCount`].
+    pub source_fps: Option<f32>,
+    /// If set, no events are played back until this warm-up period has passed, giving the app time to stabilize.
+    ///
+    /// The recording is delayed rather than skipped, and its timestamps are left unchanged. See [`WarmupProgress`].
+    pub warmup: Option<PlaybackWarmup>,
+    /// If `true`, playback is reloaded and restarted whenever the file being played back changes on disk.
+    ///
+    /// This only has an effect when playing back from an [`InputPlaybackSource::File`]. See [`WatchPlaybackFile`].
+    #[cfg(feature = "watch")]
+    pub watch_file: bool,
 }
 
 impl BeginInputPlayback {
@@ -65,13 +220,26 @@ impl BeginInputPlayback {
     pub fn observer(trigger: Trigger<BeginInputPlayback>, mut commands: Commands) {
         let event = trigger.event();
         commands.init_resource::<PlaybackProgress>();
+        commands.insert_resource(IdleMarkerDelay::default());
+        commands.insert_resource(DeferredPlaybackEvents::default());
+        commands.insert_resource(PlaybackActivity::default());
+        commands.insert_resource(LastPlaybackFrame::default());
+        commands.remove_resource::<PlaybackTimeOffset>();
         commands.insert_resource(event.playback_strategy);
 
         if let Some(source) = event.source.as_ref() {
             let timestamped_inputs = match source {
                 InputPlaybackSource::TimestampedInputs(inputs) => inputs.clone(),
+                #[cfg(feature = "file")]
                 InputPlaybackSource::File(playback_path) => {
                     commands.insert_resource(playback_path.clone());
+                    #[cfg(feature = "watch")]
+                    if let (true, Some(path)) = (event.watch_file, playback_path.path()) {
+                        match WatchPlaybackFile::new(path) {
+                            Ok(watch) => commands.insert_resource(watch),
+                            Err(error) => warn!("Could not watch {path:?} for changes: {error}"),
+                        }
+                    }
                     deserialize_timestamped_inputs(playback_path)
                         .unwrap()
                         .unwrap()
@@ -83,6 +251,71 @@ impl BeginInputPlayback {
         if let Some(playback_window) = &event.playback_window {
             commands.insert_resource(playback_window.clone());
         }
+        commands.insert_resource(event.retargeted_inputs.clone());
+        commands.insert_resource(event.cursor_origin);
+        if event.respect_recorded_duration {
+            commands.insert_resource(RespectRecordedDuration);
+        } else {
+            commands.remove_resource::<RespectRecordedDuration>();
+        }
+
+        if event.immediate_input_update {
+            commands.insert_resource(ImmediateInputUpdate);
+        } else {
+            commands.remove_resource::<ImmediateInputUpdate>();
+        }
+        if event.fixed_timestep {
+            commands.insert_resource(PlaybackInFixedTimestep);
+        } else {
+            commands.remove_resource::<PlaybackInFixedTimestep>();
+        }
+        match event.source_fps {
+            Some(source_fps) => {
+                if event.playback_strategy != PlaybackStrategy::RecordedFrameRate {
+                    warn!(
+                        "source_fps only affects PlaybackStrategy::RecordedFrameRate, and is ignored while {}",
+                        event.playback_strategy
+                    );
+                }
+                if event.respect_recorded_duration || event.use_sim_tick {
+                    warn!("respect_recorded_duration and use_sim_tick only affect PlaybackStrategy::FrameCount, and are ignored when playing back at the source_fps");
+                }
+                commands.insert_resource(PlaybackSourceFrameRate(source_fps));
+            }
+            None => commands.remove_resource::<PlaybackSourceFrameRate>(),
+        }
+        if event.use_sim_tick {
+            commands.insert_resource(PlaybackSimTicks);
+        } else {
+            commands.remove_resource::<PlaybackSimTicks>();
+        }
+        match event.warmup {
+            Some(warmup) => commands.insert_resource(WarmupProgress::new(warmup)),
+            None => commands.remove_resource::<WarmupProgress>(),
+        }
+
+        if !event.skip_validation {
+            commands.queue(warn_if_zero_span);
+        }
+
+        #[cfg(feature = "file")]
+        if event.resume {
+            commands.queue(resume_from_checkpoint);
+        }
+    }
+}
+
+/// Logs a warning if the [`TimestampedInputs`] to be played back have a zero span, as this usually indicates a corrupted recording.
+fn warn_if_zero_span(world: &mut World) {
+    let Some(timestamped_input) = world.get_resource::<TimestampedInputs>() else {
+        return;
+    };
+
+    if timestamped_input.has_zero_span() {
+        warn!(
+            "All {} events in the recording share the same frame and time, so they will be played back at once. The recording may be corrupted.",
+            timestamped_input.len()
+        );
     }
 }
 
@@ -92,15 +325,17 @@ impl BeginInputPlayback {
 #[derive(Debug)]
 pub enum InputPlaybackSource {
     /// Reads from a file and deserializes the content into a `TimestampedInputs`.
+    #[cfg(feature = "file")]
     File(PlaybackFilePath),
     /// Uses the provided `TimestampedInputs` parameter as the source of input data.
     TimestampedInputs(TimestampedInputs),
 }
 
 impl InputPlaybackSource {
-    /// Reads source data from a file using the provided filepath.
-    pub fn from_file(filepath: impl Into<String>) -> Self {
-        InputPlaybackSource::File(PlaybackFilePath::new(&filepath.into()))
+    /// Reads source data from a file using the provided filepath, such as a `&str`, [`String`] or [`PathBuf`].
+    #[cfg(feature = "file")]
+    pub fn from_file(filepath: impl Into<PathBuf>) -> Self {
+        InputPlaybackSource::File(PlaybackFilePath::new(filepath))
     }
 
     /// Defines source data using raw data.
@@ -122,11 +357,140 @@ pub struct EndInputPlayback;
 impl EndInputPlayback {
     /// An `ObserverSystem` for `EndInputPlayback` that removes playback-related resources including previously-recorded inputs.
     fn observer(_trigger: Trigger<EndInputPlayback>, mut commands: Commands) {
+        #[cfg(feature = "file")]
         commands.remove_resource::<PlaybackFilePath>();
+        #[cfg(feature = "file")]
+        commands.remove_resource::<StreamingPlaybackSource>();
         commands.remove_resource::<TimestampedInputs>();
         commands.remove_resource::<PlaybackProgress>();
         commands.remove_resource::<PlaybackStrategy>();
         commands.remove_resource::<PlaybackWindow>();
+        commands.remove_resource::<RetargetedInputs>();
+        commands.remove_resource::<CursorOrigin>();
+        commands.remove_resource::<RespectRecordedDuration>();
+        commands.remove_resource::<PlaybackSimTicks>();
+        commands.remove_resource::<ImmediateInputUpdate>();
+        commands.remove_resource::<PlaybackInFixedTimestep>();
+        commands.remove_resource::<PlaybackSourceFrameRate>();
+        commands.remove_resource::<IdleMarkerDelay>();
+        commands.remove_resource::<DeferredPlaybackEvents>();
+        commands.remove_resource::<PlaybackTimeOffset>();
+        commands.remove_resource::<WarmupProgress>();
+        commands.remove_resource::<PlaybackActivity>();
+        commands.remove_resource::<LastPlaybackFrame>();
+        #[cfg(feature = "watch")]
+        commands.remove_resource::<WatchPlaybackFile>();
+    }
+}
+
+/// An Observer that users can trigger to change the [`PlaybackWindow`] while playback is running.
+///
+/// Events that are played back afterwards are sent to the new window, according to the [`RetargetedInputs`].
+#[derive(Debug, Clone, Event)]
+pub struct SetPlaybackWindow(pub PlaybackWindow);
+
+impl SetPlaybackWindow {
+    /// An `ObserverSystem` for `SetPlaybackWindow` that replaces the [`PlaybackWindow`] resource.
+    fn observer(trigger: Trigger<SetPlaybackWindow>, mut commands: Commands) {
+        commands.insert_resource(trigger.event().0.clone());
+    }
+}
+
+/// An Observer that users can trigger to reload the recording at the stored [`PlaybackFilePath`] and restart playback from its beginning.
+///
+/// This is triggered automatically when the file changes if a [`WatchPlaybackFile`] resource exists.
+#[cfg(feature = "file")]
+#[derive(Debug, Event)]
+pub struct ReloadPlaybackFile;
+
+#[cfg(feature = "file")]
+impl ReloadPlaybackFile {
+    /// An `ObserverSystem` for `ReloadPlaybackFile` that replaces the [`TimestampedInputs`] with the contents of the file, and resets the [`PlaybackProgress`].
+    fn observer(_trigger: Trigger<ReloadPlaybackFile>, mut commands: Commands) {
+        commands.queue(reload_playback_file);
+    }
+}
+
+/// Reloads the [`TimestampedInputs`] from the stored [`PlaybackFilePath`], restarting playback.
+///
+/// If the file cannot be read, a warning is logged and playback continues unchanged.
+#[cfg(feature = "file")]
+fn reload_playback_file(world: &mut World) {
+    let Some(playback_path) = world.get_resource::<PlaybackFilePath>() else {
+        warn!("Playback cannot be reloaded without a PlaybackFilePath resource");
+        return;
+    };
+    let timestamped_inputs = match deserialize_timestamped_inputs(playback_path) {
+        Some(Ok(timestamped_inputs)) => timestamped_inputs,
+        Some(Err(error)) => {
+            warn!("Could not reload playback file: {error:?}");
+            return;
+        }
+        None => return,
+    };
+
+    world.insert_resource(timestamped_inputs);
+    world.insert_resource(PlaybackProgress::default());
+}
+
+/// If this resource exists, the file being played back is watched for changes,
+/// triggering a [`ReloadPlaybackFile`] whenever it is modified.
+///
+/// This is inserted by [`BeginInputPlayback::watch_file`], and is intended for rapid iteration during development.
+#[cfg(feature = "watch")]
+#[derive(Resource)]
+pub struct WatchPlaybackFile {
+    /// The watcher must be kept alive for as long as changes should be reported.
+    _watcher: notify::RecommendedWatcher,
+    changes: std::sync::Mutex<std::sync::mpsc::Receiver<notify::Result<notify::Event>>>,
+}
+
+#[cfg(feature = "watch")]
+impl WatchPlaybackFile {
+    /// Begins watching the file at the provided `path` for changes.
+    pub fn new(path: &Path) -> notify::Result<Self> {
+        use notify::Watcher;
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender)?;
+        watcher.watch(path, notify::RecursiveMode::NonRecursive)?;
+
+        Ok(WatchPlaybackFile {
+            _watcher: watcher,
+            changes: std::sync::Mutex::new(receiver),
+        })
+    }
+
+    /// Has the file been created or modified since this was last checked?
+    fn has_changed(&self) -> bool {
+        let Ok(changes) = self.changes.lock() else {
+            return false;
+        };
+        // Editors often save files in several steps, so all pending changes are drained at once
+        changes
+            .try_iter()
+            .fold(false, |changed, change| match change {
+                Ok(event) => changed || event.kind.is_create() || event.kind.is_modify(),
+                Err(error) => {
+                    warn!("Error while watching the playback file: {error}");
+                    changed
+                }
+            })
+    }
+}
+
+#[cfg(feature = "watch")]
+impl std::fmt::Debug for WatchPlaybackFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("WatchPlaybackFile").finish_non_exhaustive()
+    }
+}
+
+/// Triggers a [`ReloadPlaybackFile`] whenever the [`WatchPlaybackFile`] reports that the file has changed.
+#[cfg(feature = "watch")]
+pub fn reload_changed_playback_file(watch: Res<WatchPlaybackFile>, mut commands: Commands) {
+    if watch.has_changed() {
+        commands.trigger(ReloadPlaybackFile);
     }
 }
 
@@ -146,6 +510,49 @@ pub enum PlaybackWindow {
     Window(Entity),
 }
 
+/// The kinds of input events that are retargeted to the [`PlaybackWindow`], configured as a resource.
+///
+/// Event kinds that are not retargeted keep the window entity that they were recorded with.
+/// By default, keyboard and mouse button events are retargeted, but cursor events are not.
+///
+/// Replayed [`CursorMoved`] events also set the cursor position of the window they are sent to, after any retargeting.
+/// Unless cursor events are retargeted, each window therefore keeps its own cursor position during multi-window playback.
+#[derive(Resource, Debug, PartialEq, Eq, Clone)]
+pub struct RetargetedInputs {
+    /// Mouse buttons and mouse wheel inputs
+    pub mouse_buttons: bool,
+    /// Moving the mouse
+    pub mouse_motion: bool,
+    /// Keyboard inputs
+    pub keyboard: bool,
+}
+
+impl RetargetedInputs {
+    /// No input events are retargeted
+    pub const NONE: RetargetedInputs = RetargetedInputs {
+        mouse_buttons: false,
+        mouse_motion: false,
+        keyboard: false,
+    };
+
+    /// All input events that target a window are retargeted
+    pub const ALL: RetargetedInputs = RetargetedInputs {
+        mouse_buttons: true,
+        mouse_motion: true,
+        keyboard: true,
+    };
+}
+
+impl Default for RetargetedInputs {
+    fn default() -> Self {
+        RetargetedInputs {
+            mouse_buttons: true,
+            mouse_motion: false,
+            keyboard: true,
+        }
+    }
+}
+
 /// Controls the approach used for playing back recorded inputs
 ///
 /// [`PlaybackStrategy::Time`] is the default strategy.
@@ -160,6 +567,12 @@ pub enum PlaybackStrategy {
     ///
     /// This strategy is faster, as you can turn off any frame rate limiting mechanism.
     FrameCount,
+    /// Plays events frame by frame, at the frame rate at which they were originally captured.
+    ///
+    /// Real time is accumulated, and recorded frames are only advanced at the cadence given by [`TimestampedInputs::recorded_frame_duration`],
+    /// regardless of the current frame rate of the app.
+    /// If the recording's frame rate is known, it can be provided with [`BeginInputPlayback::source_fps`] instead.
+    RecordedFrameRate,
     /// Plays events between the first and second [`Duration`] once, measured in time since app startup.
     ///
     /// The events are played back at the same rate they were captured.
@@ -182,12 +595,548 @@ pub enum PlaybackStrategy {
     /// This range includes events sent at the start of the range, but not the end.
     /// There will always be one frame between the end of the previous loop and the start of the next.
     FrameRangeLoop(FrameCount, FrameCount),
+    /// Plays events one recorded frame at a time, only advancing when the [`PlaybackGate`] is open.
+    ///
+    /// Each time the gate is opened, all events from the next recorded frame are played back, and the gate is closed again.
+    /// This is useful for stepping through a recording from your own UI.
+    Gated,
+    /// Plays all remaining events in the first update, then switches to [`PlaybackStrategy::Paused`].
+    ///
+    /// This is useful for tests that only care about the final input state, and not the timing of the inputs.
+    Immediate,
     /// Does not playback any events.
     ///
     /// This is useful for interactive use cases, to temporarily disable sending events.
     Paused,
 }
 
+impl std::fmt::Display for PlaybackStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PlaybackStrategy::Time => write!(f, "Playing by time"),
+            PlaybackStrategy::FrameCount => write!(f, "Playing by frame"),
+            PlaybackStrategy::RecordedFrameRate => write!(f, "Playing at the recorded frame rate"),
+            PlaybackStrategy::TimeRangeOnce(start, end) => {
+                write!(f, "Playing {start:?}–{end:?} once")
+            }
+            PlaybackStrategy::TimeRangeLoop(start, end) => write!(f, "Looping {start:?}–{end:?}"),
+            PlaybackStrategy::FrameRangeOnce(start, end) => {
+                write!(f, "Playing frames {}–{} once", start.0, end.0)
+            }
+            PlaybackStrategy::FrameRangeLoop(start, end) => {
+                write!(f, "Looping frames {}–{}", start.0, end.0)
+            }
+            PlaybackStrategy::Gated => write!(f, "Waiting for the playback gate"),
+            PlaybackStrategy::Immediate => write!(f, "Playing all events at once"),
+            PlaybackStrategy::Paused => write!(f, "Paused"),
+        }
+    }
+}
+
+/// Controls when [`PlaybackStrategy::Gated`] advances to the next recorded frame.
+///
+/// Set this to `true` to play back a single recorded frame: it is reset to `false` once that frame has been played.
+/// If this resource is absent, the gate is treated as closed.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PlaybackGate(pub bool);
+
+/// Shifts the playback window of [`PlaybackStrategy::Time`] forward by the stored [`Duration`].
+///
+/// Events are played back `lookahead` earlier than they were recorded,
+/// which is useful for netcode that wants to receive inputs slightly ahead of time to hide latency.
+/// As the window only ever moves forward, events are never skipped or played back twice.
+///
+/// If this resource is absent, no lookahead is applied.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PlaybackLookahead(pub Duration);
+
+/// Recorded frames at which playback pauses, like the breakpoints of a debugger.
+///
+/// When playback reaches or crosses a breakpoint frame, the [`PlaybackStrategy`] is set to [`PlaybackStrategy::Paused`]
+/// and a [`PlaybackBreakpointHit`] event is sent, even if nothing was recorded on that frame.
+/// The events of the breakpoint frame are played back, but events from later frames that were due in the same update,
+/// such as when the app lags behind the recording, are held back by leaving the cursor on them.
+/// Restoring the previous strategy resumes playback from the following frame.
+/// The time range strategies track their own progress, so held back events are skipped rather than resumed under them.
+///
+/// If this resource is absent, playback never pauses on its own.
+/// As [`FrameCount`] does not implement [`Hash`], breakpoints are stored in a [`BTreeSet`].
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Default)]
+pub struct PlaybackBreakpoints(pub BTreeSet<FrameCount>);
+
+/// Sent when playback pauses at one of the [`PlaybackBreakpoints`], storing the breakpoint's recorded frame.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlaybackBreakpointHit(pub FrameCount);
+
+/// The recorded frame that playback had reached by the end of the previous update, if any.
+///
+/// This is compared against the frame reached in the current update to detect when [`PlaybackBreakpoints`] are crossed.
+/// Strategies that do not follow recorded frames, such as [`PlaybackStrategy::Time`], report the frame of the last event they played back.
+/// This is inserted by [`BeginInputPlayback`] and removed by [`EndInputPlayback`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LastPlaybackFrame(pub Option<FrameCount>);
+
+/// Is the next recorded event due within the current update's playback window?
+///
+/// This is updated by [`playback_timestamped_input`] using the bounds of the active [`PlaybackStrategy`],
+/// and is `false` during idle stretches of the recording, while paused and while warming up.
+/// Use it to drive "replay is idle" indicators.
+/// This is inserted by [`BeginInputPlayback`] and removed by [`EndInputPlayback`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PlaybackActivity(pub bool);
+
+/// Limits how many events [`playback_timestamped_input`] plays back in a single update.
+///
+/// Events past the limit are stored in the [`DeferredPlaybackEvents`] and played back on the following updates, in their recorded order.
+/// The [`PlaybackStrategy`] keeps advancing through the recording as usual, so deferred events are only delayed, never skipped.
+/// This prevents systems from stalling when a large number of events are played back at once, such as when fast-forwarding.
+///
+/// If this resource is absent, every event is played back as soon as the [`PlaybackStrategy`] reaches it.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaxEventsPerFrame(pub usize);
+
+/// Events that were held back by the [`MaxEventsPerFrame`], waiting to be played back.
+///
+/// This is reset by [`BeginInputPlayback`], and cleared whenever playback is moved by the [`PlaybackControls`].
+#[derive(Resource, Debug, Clone, PartialEq, Default)]
+pub struct DeferredPlaybackEvents(pub VecDeque<TimestampedInputEvent>);
+
+/// Randomly drops a fraction of the events played back by [`playback_timestamped_input`],
+/// to test how an app copes with lost inputs, such as over a flaky network.
+///
+/// Events are dropped using a random number generator seeded with the `seed`, so the same events are dropped on every run.
+/// Releases of dropped presses are dropped as well, so that buttons are never released without having been pressed.
+///
+/// If this resource is absent, no events are dropped.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct PlaybackDropRate {
+    /// The fraction of events that are dropped, between `0.0` and `1.0`.
+    pub rate: f32,
+    /// The seed of the random number generator, set when this resource is created.
+    pub seed: u64,
+    rng_state: u64,
+    dropped_presses: HashSet<ButtonlikeInput>,
+}
+
+impl PlaybackDropRate {
+    /// Creates a new [`PlaybackDropRate`] that drops the `rate` fraction of events, using the provided `seed`.
+    pub fn new(rate: f32, seed: u64) -> Self {
+        PlaybackDropRate {
+            rate,
+            seed,
+            rng_state: seed,
+            dropped_presses: HashSet::default(),
+        }
+    }
+
+    /// Should the provided `input_event` be dropped?
+    ///
+    /// This advances the random number generator once for every event that is not the release of a dropped press.
+    pub fn should_drop(&mut self, input_event: &InputEvent) -> bool {
+        if let Some((input, ButtonState::Released)) = input_event.buttonlike_state() {
+            if self.dropped_presses.remove(&input) {
+                return true;
+            }
+        }
+
+        let dropped = self.next_f32() < self.rate;
+        if let Some((input, ButtonState::Pressed)) = input_event.buttonlike_state() {
+            match dropped {
+                true => self.dropped_presses.insert(input),
+                false => self.dropped_presses.remove(&input),
+            };
+        }
+        dropped
+    }
+
+    /// Returns a random number in `[0, 1)`, using the `SplitMix64` generator.
+    fn next_f32(&mut self) -> f32 {
+        self.rng_state = self.rng_state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.rng_state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        (z >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+/// Forwards every event played back by [`playback_timestamped_input`] to a channel, in the order they are played back.
+///
+/// This is useful to log the output of playback, or to forward it over a network.
+/// Events are forwarded as recorded, before they are retargeted to any [`PlaybackWindow`].
+/// If the receiver has been dropped, events are no longer forwarded.
+#[derive(Resource, Debug, Clone)]
+pub struct PlaybackChannel {
+    /// The sending half of the channel that played back events are forwarded to.
+    pub sender: Sender<InputEvent>,
+    /// Should played back events also be sent to the app as usual?
+    ///
+    /// If `false`, events are only forwarded to the channel.
+    pub send_to_app: bool,
+}
+
+impl PlaybackChannel {
+    /// Creates a new [`PlaybackChannel`] that forwards played back events to the `sender`, in addition to sending them to the app.
+    pub fn new(sender: Sender<InputEvent>) -> Self {
+        PlaybackChannel {
+            sender,
+            send_to_app: true,
+        }
+    }
+}
+
+/// If this resource is present, replayed gamepad axis events also directly update the [`Gamepad`] component's axis values.
+///
+/// Bevy only updates [`Gamepad`] components from raw gamepad events,
+/// so without this resource, systems reading the [`Gamepad`] component will not see replayed axis values.
+/// With it, these values are consistent with the replayed events in the same frame.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SyncGamepadAxes;
+
+/// If this resource is present, [`PlaybackStrategy::FrameCount`] playback is throttled so that it never outpaces the recording.
+///
+/// Events are only played back once both their [`FrameCount`] and their time since startup have been reached,
+/// so a recording made at a low frame rate takes at least as long to replay as it did to record.
+/// This is inserted by [`BeginInputPlayback::respect_recorded_duration`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RespectRecordedDuration;
+
+/// The number of frames for which [`PlaybackStrategy::FrameCount`] playback has been held at [`InputEvent::IdleMarker`]s.
+///
+/// Idle markers act as timing anchors: playback does not pass a marker until its recorded time since startup has been reached,
+/// and the frames spent waiting are subtracted from the current frame, so the inputs after an idle period keep their recorded spacing.
+/// Without markers, an idle period only lasts as many frames as were recorded, however long those frames took.
+/// This is reset by [`BeginInputPlayback`], and is not used together with [`RespectRecordedDuration`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct IdleMarkerDelay(pub u32);
+
+/// How long playback waits before playing back any events, see [`BeginInputPlayback::warmup`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaybackWarmup {
+    /// Wait for this many updates.
+    Frames(u32),
+    /// Wait until this much [`Time`] has elapsed.
+    Duration(Duration),
+}
+
+/// Tracks how much of the [`PlaybackWarmup`] has passed.
+///
+/// While warming up, [`playback_timestamped_input`] plays back nothing, and the range strategies and [`PlaybackStrategy::RecordedFrameRate`] do not advance.
+/// Afterwards, [`PlaybackStrategy::FrameCount`] and [`PlaybackStrategy::Time`] are delayed by the frames and time spent warming up,
+/// so the recording starts from its first event.
+/// This is inserted by [`BeginInputPlayback::warmup`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WarmupProgress {
+    /// The warm-up period to wait for.
+    pub warmup: PlaybackWarmup,
+    /// The number of updates spent warming up so far.
+    pub elapsed_frames: u32,
+    /// The amount of time spent warming up so far.
+    pub elapsed_time: Duration,
+}
+
+impl WarmupProgress {
+    /// Creates a new [`WarmupProgress`] at the start of the `warmup` period.
+    pub fn new(warmup: PlaybackWarmup) -> Self {
+        WarmupProgress {
+            warmup,
+            elapsed_frames: 0,
+            elapsed_time: Duration::ZERO,
+        }
+    }
+
+    /// Has the warm-up period passed?
+    pub fn is_finished(&self) -> bool {
+        match self.warmup {
+            PlaybackWarmup::Frames(frames) => self.elapsed_frames >= frames,
+            PlaybackWarmup::Duration(duration) => self.elapsed_time >= duration,
+        }
+    }
+}
+
+/// Snapshots of the [`AccumulatedMouseMotion`] and [`AccumulatedMouseScroll`] resources that were played back this frame.
+///
+/// Bevy recomputes these resources from mouse events in [`PreUpdate`],
+/// so replayed snapshots are stored here and written into the resources by [`restore_accumulated_mouse`] once that has happened.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Default)]
+pub struct ReplayedAccumulatedMouse {
+    /// The replayed [`AccumulatedMouseMotion`], if any was played back this frame.
+    pub motion: Option<AccumulatedMouseMotion>,
+    /// The replayed [`AccumulatedMouseScroll`], if any was played back this frame.
+    pub scroll: Option<AccumulatedMouseScroll>,
+}
+
+/// A system that writes any [`ReplayedAccumulatedMouse`] snapshots into the [`AccumulatedMouseMotion`] and [`AccumulatedMouseScroll`] resources.
+///
+/// Runs in [`PreUpdate`] after [`InputSystem`], so that the replayed values are not overwritten.
+pub fn restore_accumulated_mouse(
+    mut replayed: ResMut<ReplayedAccumulatedMouse>,
+    mut accumulated_mouse_motion: ResMut<AccumulatedMouseMotion>,
+    mut accumulated_mouse_scroll: ResMut<AccumulatedMouseScroll>,
+) {
+    if let Some(motion) = replayed.motion.take() {
+        *accumulated_mouse_motion = motion;
+    }
+    if let Some(scroll) = replayed.scroll.take() {
+        *accumulated_mouse_scroll = scroll;
+    }
+}
+
+/// If this resource is present, [`PlaybackStrategy::FrameCount`] plays back events up to the current [`SimTick`], rather than the current [`FrameCount`].
+///
+/// This should be used to play back recordings captured with [`CaptureSimTicks`](crate::input_capture::CaptureSimTicks).
+/// If there is no [`SimTick`] resource, the [`FrameCount`] is used instead.
+/// This is inserted by [`BeginInputPlayback::use_sim_tick`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PlaybackSimTicks;
+
+/// The frame rate, in frames per second, at which the recording being played back was captured.
+///
+/// If this resource is present, [`PlaybackStrategy::RecordedFrameRate`] uses this frame rate
+/// instead of estimating it with [`TimestampedInputs::recorded_frame_duration`].
+/// A recording captured at 30 fps therefore still takes one second to replay 30 frames when the app runs at 144 fps.
+/// Other strategies ignore this resource.
+/// This is inserted by [`BeginInputPlayback::source_fps`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Default)]
+pub struct PlaybackSourceFrameRate(pub f32);
+
+impl PlaybackSourceFrameRate {
+    /// The duration of a single recorded frame, or `None` if the frame rate is not positive and finite.
+    pub fn frame_duration(&self) -> Option<Duration> {
+        Duration::try_from_secs_f64(1. / self.0 as f64)
+            .ok()
+            .filter(|duration| !duration.is_zero())
+    }
+}
+
+/// The duration of each recorded frame under [`PlaybackStrategy::RecordedFrameRate`].
+///
+/// This is taken from the [`PlaybackSourceFrameRate`] if present and valid, and estimated from the recording otherwise.
+fn recorded_frame_duration(
+    timestamped_input: &TimestampedInputs,
+    source_frame_rate: Option<&PlaybackSourceFrameRate>,
+) -> Option<Duration> {
+    source_frame_rate
+        .and_then(PlaybackSourceFrameRate::frame_duration)
+        .or_else(|| timestamped_input.recorded_frame_duration())
+}
+
+/// If this resource is present, [`playback_timestamped_input`] runs in [`FixedFirst`] rather than in [`First`].
+///
+/// Systems in [`FixedUpdate`](bevy::app::FixedUpdate) may run zero or several times per frame,
+/// so events played back once per frame are not reliably aligned with the fixed timestep that consumes them.
+/// With this resource, events are played back at the start of each fixed timestep instead,
+/// where [`PlaybackStrategy::Time`] follows [`Time<Fixed>`](bevy::time::Fixed).
+/// This is inserted by [`BeginInputPlayback::fixed_timestep`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PlaybackInFixedTimestep;
+
+/// If this resource is present, Bevy's keyboard and mouse button input systems are also run in [`First`], right after [`playback_timestamped_input`].
+///
+/// Normally, replayed events only update [`ButtonInput`] once [`InputSystem`] runs in [`PreUpdate`],
+/// so systems in [`First`] observe the previous frame's button state.
+/// With this resource, replayed presses and releases are reflected immediately, which simplifies deterministic tests.
+/// This is inserted by [`BeginInputPlayback::immediate_input_update`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ImmediateInputUpdate;
+
+/// The buttons that were just pressed or released when [`ButtonInput<T>`] was updated in [`First`].
+///
+/// Bevy's input systems run again in [`PreUpdate`], clearing these transitions without re-adding them,
+/// so they are restored afterwards.
+#[derive(Resource, Debug)]
+struct ImmediateButtonTransitions<T> {
+    just_pressed: Vec<T>,
+    just_released: Vec<T>,
+}
+
+impl<T> Default for ImmediateButtonTransitions<T> {
+    fn default() -> Self {
+        Self {
+            just_pressed: Vec::new(),
+            just_released: Vec::new(),
+        }
+    }
+}
+
+fn snapshot_button_transitions<T: Copy + Eq + Hash + Send + Sync + 'static>(
+    input: Res<ButtonInput<T>>,
+    mut transitions: ResMut<ImmediateButtonTransitions<T>>,
+) {
+    transitions.just_pressed = input.get_just_pressed().copied().collect();
+    transitions.just_released = input.get_just_released().copied().collect();
+}
+
+fn restore_button_transitions<T: Copy + Eq + Hash + Send + Sync + 'static>(
+    mut input: ResMut<ButtonInput<T>>,
+    mut transitions: ResMut<ImmediateButtonTransitions<T>>,
+) {
+    for button in core::mem::take(&mut transitions.just_pressed) {
+        if input.pressed(button) && !input.just_pressed(button) {
+            input.reset(button);
+            input.press(button);
+        }
+    }
+    for button in core::mem::take(&mut transitions.just_released) {
+        if !input.pressed(button) && !input.just_released(button) {
+            let was_just_pressed = input.just_pressed(button);
+            input.press(button);
+            input.release(button);
+            if !was_just_pressed {
+                input.clear_just_pressed(button);
+            }
+        }
+    }
+}
+
+/// Controls what happens when a replayed [`CursorMoved`] event targets a window that does not exist.
+///
+/// If this resource is absent, [`MissingWindowBehavior::Warn`] is used.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MissingWindowBehavior {
+    /// Logs a warning, and sends the event without updating any window's cursor position.
+    #[default]
+    Warn,
+    /// Silently drops the event.
+    Skip,
+    /// Silently retargets the event to the primary window, whose cursor position is updated instead.
+    ///
+    /// If there is no primary window, this behaves like [`MissingWindowBehavior::Warn`].
+    RetargetPrimary,
+}
+
+/// The coordinate origin of the cursor positions stored in recorded [`CursorMoved`] events.
+///
+/// Bevy measures cursor positions from the top-left corner of the window.
+/// Recordings that use a different convention, such as those imported from other tools,
+/// are converted to Bevy's convention on playback, using the height of the targeted window.
+///
+/// If this resource is absent, [`CursorOrigin::TopLeft`] is used.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CursorOrigin {
+    /// Positions are measured from the top-left corner of the window, with `y` increasing downwards.
+    ///
+    /// This matches Bevy, so positions are replayed unchanged.
+    #[default]
+    TopLeft,
+    /// Positions are measured from the bottom-left corner of the window, with `y` increasing upwards.
+    ///
+    /// The `y` coordinate is flipped against the window height on playback.
+    BottomLeft,
+}
+
+/// A [`SystemParam`] that bundles everything needed to control an active input playback.
+///
+/// Systems using this must only run while playback is active,
+/// as [`BeginInputPlayback`] inserts the underlying resources.
+#[derive(SystemParam)]
+pub struct PlaybackControls<'w> {
+    playback_strategy: ResMut<'w, PlaybackStrategy>,
+    timestamped_input: ResMut<'w, TimestampedInputs>,
+    playback_progress: ResMut<'w, PlaybackProgress>,
+    source_frame_rate: Option<Res<'w, PlaybackSourceFrameRate>>,
+    deferred_events: Option<ResMut<'w, DeferredPlaybackEvents>>,
+}
+
+impl PlaybackControls<'_> {
+    /// Pauses playback, see [`PlaybackStrategy::Paused`].
+    pub fn pause(&mut self) {
+        *self.playback_strategy = PlaybackStrategy::Paused;
+    }
+
+    /// Resumes playback, using the provided `playback_strategy`.
+    pub fn resume(&mut self, playback_strategy: PlaybackStrategy) {
+        *self.playback_strategy = playback_strategy;
+    }
+
+    /// Moves playback to the first event at or after the provided `frame`, returning `true` if playback could be moved.
+    ///
+    /// Seeking moves both the cursor and the [`PlaybackProgress`], so it is only supported by the strategies that are driven by that progress:
+    /// the frame and time range strategies and [`PlaybackStrategy::RecordedFrameRate`], as well as [`PlaybackStrategy::Gated`].
+    /// [`PlaybackStrategy::Time`] and [`PlaybackStrategy::FrameCount`] follow the app's own clock,
+    /// so under those strategies (or while [`PlaybackStrategy::Immediate`] or [`PlaybackStrategy::Paused`]) nothing is changed and `false` is returned.
+    pub fn seek_to_frame(&mut self, frame: FrameCount) -> bool {
+        let cursor = self
+            .timestamped_input
+            .events
+            .iter()
+            .position(|event| event.frame >= frame)
+            .unwrap_or(self.timestamped_input.len());
+        // Time-based progress is moved to the time of the event that playback continues from
+        let event_time = self
+            .timestamped_input
+            .events
+            .get(cursor)
+            .or(self.timestamped_input.events.last())
+            .map(|event| event.time_since_startup)
+            .unwrap_or_default();
+
+        match *self.playback_strategy {
+            PlaybackStrategy::FrameRangeOnce(start, _)
+            | PlaybackStrategy::FrameRangeLoop(start, _) => {
+                self.playback_progress.elapsed_frames = FrameCount(frame.0.saturating_sub(start.0));
+            }
+            PlaybackStrategy::TimeRangeOnce(start, _)
+            | PlaybackStrategy::TimeRangeLoop(start, _) => {
+                self.playback_progress.elapsed_time = event_time.saturating_sub(start);
+            }
+            PlaybackStrategy::RecordedFrameRate => {
+                let (Some((first_frame, _)), Some(frame_duration)) = (
+                    self.timestamped_input.frame_range(),
+                    recorded_frame_duration(
+                        &self.timestamped_input,
+                        self.source_frame_rate.as_deref(),
+                    ),
+                ) else {
+                    return false;
+                };
+                self.playback_progress.elapsed_time =
+                    frame_duration * frame.0.saturating_sub(first_frame.0);
+            }
+            // Gated playback always continues from the cursor
+            PlaybackStrategy::Gated => (),
+            PlaybackStrategy::Time
+            | PlaybackStrategy::FrameCount
+            | PlaybackStrategy::Immediate
+            | PlaybackStrategy::Paused => {
+                return false;
+            }
+        }
+
+        self.timestamped_input.cursor = cursor;
+        self.clear_deferred_events();
+        true
+    }
+
+    /// Restarts playback from the beginning of the recording.
+    pub fn restart(&mut self) {
+        self.playback_progress.reset(&mut self.timestamped_input);
+        self.clear_deferred_events();
+    }
+
+    /// Discards any [`DeferredPlaybackEvents`], which no longer follow on from the new playback position.
+    fn clear_deferred_events(&mut self) {
+        if let Some(deferred_events) = self.deferred_events.as_deref_mut() {
+            deferred_events.0.clear();
+        }
+    }
+
+    /// The [`PlaybackProgress`] of the current playback.
+    pub fn progress(&self) -> &PlaybackProgress {
+        &self.playback_progress
+    }
+
+    /// The current [`PlaybackStrategy`].
+    pub fn strategy(&self) -> PlaybackStrategy {
+        *self.playback_strategy
+    }
+
+    /// The index of the next event to be played back, see [`TimestampedInputs::cursor`].
+    pub fn cursor(&self) -> usize {
+        self.timestamped_input.cursor
+    }
+}
+
 /// The [`EventWriter`] types that correspond to the input event types stored in [`InputEvent`](crate::timestamped_input::InputEvent)
 #[derive(SystemParam)]
 #[allow(missing_docs)]
@@ -196,24 +1145,66 @@ pub struct InputWriters<'w, 's> {
     pub mouse_button_input: EventWriter<'w, MouseButtonInput>,
     pub mouse_wheel: EventWriter<'w, MouseWheel>,
     pub cursor_moved: EventWriter<'w, CursorMoved>,
+    pub window_moved: EventWriter<'w, WindowMoved>,
+    pub ime: EventWriter<'w, Ime>,
     pub windows: Query<'w, 's, &'static mut Window>,
     pub gamepad: EventWriter<'w, GamepadEvent>,
+    pub gamepad_connection: EventWriter<'w, GamepadConnectionEvent>,
+    pub gamepads: Query<'w, 's, &'static mut Gamepad>,
+    pub gamepad_rumble: EventWriter<'w, GamepadRumbleRequest>,
+    pub commands: Commands<'w, 's>,
     pub app_exit: EventWriter<'w, AppExit>,
+    pub replayed_accumulated_mouse: Option<ResMut<'w, ReplayedAccumulatedMouse>>,
 }
 
 // `TimestampedInputs` is an iterator, so we need mutable access to be able to track which events we've seen
 /// A system that reads from the [`TimestampedInputs`] resources and plays back the contained events.
 ///
 /// The strategy used is based on [`PlaybackStrategy`].
-/// Additionally, `Keyboard`, `MouseButton`, and `MouseWheel` events may target `Window` entities according to the [`PlaybackWindow`].
+/// Additionally, `Keyboard`, `MouseButton`, `MouseWheel` and `CursorMoved` events may target `Window` entities according to the [`PlaybackWindow`] and [`RetargetedInputs`].
 #[allow(clippy::too_many_arguments)]
 pub fn playback_timestamped_input(
     mut timestamped_input: ResMut<TimestampedInputs>,
     mut playback_strategy: ResMut<PlaybackStrategy>,
     playback_window: Option<Res<PlaybackWindow>>,
+    retargeted_inputs: Option<Res<RetargetedInputs>>,
+    (playback_lookahead, time_offset): (
+        Option<Res<PlaybackLookahead>>,
+        Option<Res<PlaybackTimeOffset>>,
+    ),
+    (sync_gamepad_axes, playback_channel): (
+        Option<Res<SyncGamepadAxes>>,
+        Option<Res<PlaybackChannel>>,
+    ),
+    (missing_window_behavior, cursor_origin): (
+        Option<Res<MissingWindowBehavior>>,
+        Option<Res<CursorOrigin>>,
+    ),
+    (respect_recorded_duration, source_frame_rate, mut idle_marker_delay, mut warmup_progress): (
+        Option<Res<RespectRecordedDuration>>,
+        Option<Res<PlaybackSourceFrameRate>>,
+        Option<ResMut<IdleMarkerDelay>>,
+        Option<ResMut<WarmupProgress>>,
+    ),
+    (playback_gate, max_events_per_frame, mut deferred_events, mut drop_rate): (
+        Option<ResMut<PlaybackGate>>,
+        Option<Res<MaxEventsPerFrame>>,
+        Option<ResMut<DeferredPlaybackEvents>>,
+        Option<ResMut<PlaybackDropRate>>,
+    ),
     time: Res<Time>,
+    (breakpoints, mut breakpoint_hits, mut playback_activity, mut last_playback_frame): (
+        Option<Res<PlaybackBreakpoints>>,
+        EventWriter<PlaybackBreakpointHit>,
+        Option<ResMut<PlaybackActivity>>,
+        Option<ResMut<LastPlaybackFrame>>,
+    ),
     primary_window: Query<Entity, (With<Window>, With<PrimaryWindow>)>,
-    frame_count: Res<FrameCount>,
+    (frame_count, sim_tick, playback_sim_ticks): (
+        Res<FrameCount>,
+        Option<Res<SimTick>>,
+        Option<Res<PlaybackSimTicks>>,
+    ),
     mut input_writers: InputWriters,
     mut playback_progress: ResMut<PlaybackProgress>,
 ) {
@@ -222,114 +1213,440 @@ pub fn playback_timestamped_input(
         Some(PlaybackWindow::Window(entity)) => Some(*entity),
         None => None,
     };
-    // We cannot store the iterator, as different opaque return types are used
-    match *playback_strategy {
+    let frame_count = match (playback_sim_ticks, sim_tick.as_deref()) {
+        (Some(_), Some(sim_tick)) => sim_tick.frame(),
+        _ => *frame_count,
+    };
+
+    // Nothing is played back until the warm-up period has passed
+    if let Some(warmup_progress) = warmup_progress.as_deref_mut() {
+        if !warmup_progress.is_finished() {
+            if let Some(playback_activity) = playback_activity.as_deref_mut() {
+                playback_activity.0 = false;
+            }
+            warmup_progress.elapsed_frames += 1;
+            warmup_progress.elapsed_time += time.delta();
+            return;
+        }
+    }
+    let (warmup_frames, warmup_time) = warmup_progress
+        .as_deref()
+        .map_or((0, Duration::ZERO), |warmup_progress| {
+            (warmup_progress.elapsed_frames, warmup_progress.elapsed_time)
+        });
+
+    // The recorded frame that playback reaches this update, for strategies that follow recorded frames
+    let mut playback_frame = None;
+
+    // We cannot store the iterator, as different opaque return types are used,
+    // so the events are collected instead
+    let mut input_events: Vec<TimestampedInputEvent> = match *playback_strategy {
         PlaybackStrategy::Time => {
-            let input_events = timestamped_input.iter_until_time(time.elapsed());
-            send_playback_events(input_events, &mut input_writers, window_override);
+            let lookahead = playback_lookahead
+                .map(|lookahead| lookahead.0)
+                .unwrap_or_default();
+            let time_offset = time_offset.map(|offset| offset.0).unwrap_or_default();
+            timestamped_input
+                .iter_until_time(
+                    time.elapsed().saturating_sub(warmup_time) + time_offset + lookahead,
+                )
+                .into_iter()
+                .collect()
         }
-        PlaybackStrategy::FrameCount => {
-            let input_events = timestamped_input.iter_until_frame(*frame_count);
-            send_playback_events(input_events, &mut input_writers, window_override);
+        PlaybackStrategy::FrameCount => match respect_recorded_duration {
+            Some(_) => {
+                let frame = FrameCount(frame_count.0.wrapping_sub(warmup_frames));
+                playback_frame = Some(frame);
+                timestamped_input
+                    .iter_until_frame_and_time(frame, time.elapsed().saturating_sub(warmup_time))
+                    .into_iter()
+                    .collect()
+            }
+            None => {
+                let delay = idle_marker_delay.as_deref().map_or(0, |delay| delay.0);
+                let frame = FrameCount(
+                    frame_count
+                        .0
+                        .wrapping_sub(delay)
+                        .wrapping_sub(warmup_frames),
+                );
+                playback_frame = Some(frame);
+                let input_events = timestamped_input
+                    .iter_until_frame_or_idle_marker(
+                        frame,
+                        time.elapsed().saturating_sub(warmup_time),
+                    )
+                    .into_iter()
+                    .collect();
+
+                // Hold playback at an idle marker until its recorded time has been reached
+                let held_at_idle_marker = timestamped_input.current_input()
+                    == Some(InputEvent::IdleMarker)
+                    && timestamped_input
+                        .current_framecount()
+                        .is_some_and(|marker_frame| marker_frame <= frame);
+                if let (true, Some(idle_marker_delay)) =
+                    (held_at_idle_marker, idle_marker_delay.as_deref_mut())
+                {
+                    idle_marker_delay.0 = idle_marker_delay.0.wrapping_add(1);
+                }
+                input_events
+            }
+        },
+        PlaybackStrategy::RecordedFrameRate => {
+            let elapsed = playback_progress.next_time(time.delta(), Duration::ZERO);
+            let recorded_frame = match (
+                timestamped_input.frame_range(),
+                recorded_frame_duration(&timestamped_input, source_frame_rate.as_deref()),
+            ) {
+                (Some((start, _)), Some(frame_duration)) => {
+                    let frames_elapsed = elapsed.as_secs_f64() / frame_duration.as_secs_f64();
+                    FrameCount(start.0.wrapping_add(frames_elapsed as u32))
+                }
+                // All events were recorded in a single frame, so they are played at once
+                (Some((start, _)), None) => start,
+                (None, _) => FrameCount(0),
+            };
+            playback_frame = Some(recorded_frame);
+
+            timestamped_input
+                .iter_until_frame(recorded_frame)
+                .into_iter()
+                .collect()
         }
         PlaybackStrategy::TimeRangeOnce(start, end) => {
-            let input_events = timestamped_input.iter_between_times(
-                playback_progress.current_time(start),
-                playback_progress.next_time(time.delta(), start),
-            );
-            send_playback_events(input_events, &mut input_writers, window_override);
+            let input_events = timestamped_input
+                .iter_between_times(
+                    playback_progress.current_time(start),
+                    playback_progress.next_time(time.delta(), start),
+                )
+                .into_iter()
+                .collect();
 
             // If we've covered the entire range, reset our progress
             if playback_progress.current_time(start) > end {
-                playback_progress.reset(timestamped_input.into_inner());
+                playback_progress.reset(&mut timestamped_input);
                 // We only want to play back once, so pause.
                 *playback_strategy = PlaybackStrategy::Paused;
             }
+            input_events
         }
         PlaybackStrategy::FrameRangeOnce(start, end) => {
-            let input_events = timestamped_input.iter_between_frames(
-                playback_progress.current_frame(start),
-                playback_progress.next_frame(start),
-            );
-            send_playback_events(input_events, &mut input_writers, window_override);
+            let window_start = playback_progress.current_frame(start);
+            let window_end = playback_progress.next_frame(start);
+            playback_frame = Some(FrameCount(window_end.0.wrapping_sub(1)));
+            let input_events = timestamped_input
+                .iter_between_frames(window_start, window_end)
+                .into_iter()
+                .collect();
 
             // If we've covered the entire range, reset our progress
             if playback_progress.current_frame(start) > end {
-                playback_progress.reset(timestamped_input.into_inner());
+                playback_progress.reset(&mut timestamped_input);
                 // We only want to play back once, so pause.
                 *playback_strategy = PlaybackStrategy::Paused;
             }
+            input_events
         }
         PlaybackStrategy::TimeRangeLoop(start, end) => {
-            let input_events = timestamped_input.iter_between_times(
-                playback_progress.current_time(start),
-                playback_progress.next_time(time.delta(), start),
-            );
-            send_playback_events(input_events, &mut input_writers, window_override);
+            let input_events = timestamped_input
+                .iter_between_times(
+                    playback_progress.current_time(start),
+                    playback_progress.next_time(time.delta(), start),
+                )
+                .into_iter()
+                .collect();
 
             // If we've covered the entire range, reset our progress
             if playback_progress.current_time(start) > end {
-                playback_progress.reset(timestamped_input.into_inner());
+                playback_progress.reset(&mut timestamped_input);
             }
+            input_events
         }
         PlaybackStrategy::FrameRangeLoop(start, end) => {
-            let input_events = timestamped_input.iter_between_frames(
-                playback_progress.current_frame(start),
-                playback_progress.next_frame(start),
-            );
-            send_playback_events(input_events, &mut input_writers, window_override);
+            let window_start = playback_progress.current_frame(start);
+            let window_end = playback_progress.next_frame(start);
+            playback_frame = Some(FrameCount(window_end.0.wrapping_sub(1)));
+            let input_events = timestamped_input
+                .iter_between_frames(window_start, window_end)
+                .into_iter()
+                .collect();
 
             // If we've covered the entire range, reset our progress
             if playback_progress.current_frame(start) > end {
-                playback_progress.reset(timestamped_input.into_inner());
+                playback_progress.reset(&mut timestamped_input);
             }
+            input_events
         }
-        PlaybackStrategy::Paused => {
-            // Do nothing
+        PlaybackStrategy::Gated => match playback_gate {
+            Some(mut playback_gate) if playback_gate.0 => {
+                playback_gate.0 = false;
+                playback_frame = timestamped_input.current_framecount();
+                match playback_frame {
+                    Some(next_frame) => timestamped_input
+                        .iter_until_frame(next_frame)
+                        .into_iter()
+                        .collect(),
+                    None => Vec::new(),
+                }
+            }
+            _ => Vec::new(),
+        },
+        PlaybackStrategy::Immediate => {
+            *playback_strategy = PlaybackStrategy::Paused;
+            timestamped_input.iter_rest().into_iter().collect()
         }
+        PlaybackStrategy::Paused => Vec::new(),
     };
+
+    if let Some(playback_activity) = playback_activity.as_deref_mut() {
+        playback_activity.0 = !input_events.is_empty();
+    }
+
+    // Pause when playback reaches or crosses a breakpoint frame
+    if let Some(mut playback_frame) =
+        playback_frame.or_else(|| input_events.last().map(|event| event.frame))
+    {
+        let previous_frame = last_playback_frame
+            .as_deref()
+            .and_then(|last_playback_frame| last_playback_frame.0)
+            // Playback has jumped backwards, such as when a loop restarts
+            .filter(|previous_frame| *previous_frame <= playback_frame);
+        let breakpoint = breakpoints.as_deref().and_then(|breakpoints| {
+            let start = previous_frame.map_or(Bound::Unbounded, Bound::Excluded);
+            breakpoints
+                .0
+                .range((start, Bound::Included(playback_frame)))
+                .next()
+                .copied()
+        });
+
+        if let Some(breakpoint) = breakpoint {
+            // Leave the cursor on the events of later frames, so that they are played back when resuming
+            let kept = input_events
+                .iter()
+                .position(|event| event.frame > breakpoint)
+                .unwrap_or(input_events.len());
+            timestamped_input.cursor -= input_events.len() - kept;
+            input_events.truncate(kept);
+
+            *playback_strategy = PlaybackStrategy::Paused;
+            breakpoint_hits.send(PlaybackBreakpointHit(breakpoint));
+            playback_frame = breakpoint;
+        }
+
+        if let Some(last_playback_frame) = last_playback_frame.as_deref_mut() {
+            last_playback_frame.0 = Some(playback_frame);
+        }
+    }
+
+    // Events past the limit are deferred, behind any events that were deferred on earlier updates
+    let input_events: Vec<TimestampedInputEvent> = match deferred_events.as_deref_mut() {
+        Some(deferred_events) => {
+            deferred_events.0.extend(input_events);
+            let max_events = max_events_per_frame.map_or(usize::MAX, |max_events| max_events.0);
+            let count = deferred_events.0.len().min(max_events);
+            deferred_events.0.drain(..count).collect()
+        }
+        None => input_events,
+    };
+
+    let input_events: Vec<InputEvent> = input_events
+        .into_iter()
+        .map(|event| event.input_event)
+        .filter(|input_event| {
+            drop_rate
+                .as_deref_mut()
+                .is_none_or(|drop_rate| !drop_rate.should_drop(input_event))
+        })
+        .collect();
+
+    if let Some(playback_channel) = playback_channel.as_deref() {
+        for input_event in &input_events {
+            // A dropped receiver is not an error: it simply stops listening
+            let _ = playback_channel.sender.send(input_event.clone());
+        }
+        if !playback_channel.send_to_app {
+            return;
+        }
+    }
+
+    send_playback_events(
+        input_events,
+        &mut input_writers,
+        window_override,
+        &retargeted_inputs.as_deref().cloned().unwrap_or_default(),
+        sync_gamepad_axes.is_some(),
+        missing_window_behavior
+            .as_deref()
+            .copied()
+            .unwrap_or_default(),
+        cursor_origin.as_deref().copied().unwrap_or_default(),
+        primary_window.get_single().ok(),
+    );
+}
+
+/// Immediately sends each of the provided `input_events` to the corresponding [`Events`] resource,
+/// without going through the playback lifecycle.
+///
+/// Events are sent unchanged: they are not retargeted to any [`PlaybackWindow`].
+pub fn inject_events(world: &mut World, input_events: impl IntoIterator<Item = InputEvent>) {
+    let mut system_state = SystemState::<InputWriters>::new(world);
+    let mut input_writers = system_state.get_mut(world);
+    send_playback_events(
+        input_events,
+        &mut input_writers,
+        None,
+        &RetargetedInputs::NONE,
+        false,
+        MissingWindowBehavior::Warn,
+        CursorOrigin::TopLeft,
+        None,
+    );
+    system_state.apply(world);
 }
 
+#[allow(clippy::too_many_arguments)]
 fn send_playback_events(
-    timestamped_input_events: impl IntoIterator<Item = TimestampedInputEvent>,
+    input_events: impl IntoIterator<Item = InputEvent>,
     input_writers: &mut InputWriters,
     window_override: Option<Entity>,
+    retargeted_inputs: &RetargetedInputs,
+    sync_gamepad_axes: bool,
+    missing_window_behavior: MissingWindowBehavior,
+    cursor_origin: CursorOrigin,
+    primary_window: Option<Entity>,
 ) {
-    for timestamped_input_event in timestamped_input_events {
+    let keyboard_override = window_override.filter(|_| retargeted_inputs.keyboard);
+    let mouse_buttons_override = window_override.filter(|_| retargeted_inputs.mouse_buttons);
+    let mouse_motion_override = window_override.filter(|_| retargeted_inputs.mouse_motion);
+
+    for input_event in input_events {
         use crate::timestamped_input::InputEvent::*;
-        match timestamped_input_event.input_event {
+        match input_event {
             Keyboard(mut e) => {
-                if let Some(entity) = window_override {
+                if let Some(entity) = keyboard_override {
                     e.window = entity;
                 }
                 input_writers.keyboard_input.send(e);
             }
+            Ime(mut e) => {
+                if let Some(entity) = keyboard_override {
+                    match &mut e {
+                        bevy::window::Ime::Preedit { window, .. }
+                        | bevy::window::Ime::Commit { window, .. }
+                        | bevy::window::Ime::Enabled { window }
+                        | bevy::window::Ime::Disabled { window } => *window = entity,
+                    }
+                }
+                input_writers.ime.send(e);
+            }
             MouseButton(mut e) => {
-                if let Some(entity) = window_override {
+                if let Some(entity) = mouse_buttons_override {
                     e.window = entity;
                 }
                 input_writers.mouse_button_input.send(e);
             }
             MouseWheel(mut e) => {
-                if let Some(entity) = window_override {
+                if let Some(entity) = mouse_buttons_override {
                     e.window = entity;
                 }
                 input_writers.mouse_wheel.send(e);
             }
             // Window events MUST update the `Window` struct itself
             // BLOCKED: https://github.com/bevyengine/bevy/issues/6163
-            CursorMoved(e) => {
+            CursorMoved(mut e) => {
+                if let Some(entity) = mouse_motion_override {
+                    e.window = entity;
+                }
+                if input_writers.windows.get(e.window).is_err() {
+                    match (missing_window_behavior, primary_window) {
+                        (MissingWindowBehavior::Skip, _) => continue,
+                        (MissingWindowBehavior::RetargetPrimary, Some(entity)) => {
+                            e.window = entity;
+                        }
+                        _ => {
+                            warn!("Window entity was not found when attempting to play back {e:?}")
+                        }
+                    }
+                }
                 if let Ok(mut window) = input_writers.windows.get_mut(e.window) {
+                    if cursor_origin == CursorOrigin::BottomLeft {
+                        e.position.y = window.height() - e.position.y;
+                        if let Some(delta) = e.delta.as_mut() {
+                            delta.y = -delta.y;
+                        }
+                    }
                     window.set_cursor_position(Some(e.position));
-                } else {
-                    warn!("Window entity was not found when attempting to play back {e:?}")
                 }
 
                 input_writers.cursor_moved.send(e);
             }
             Gamepad(e) => {
+                match &e {
+                    GamepadEvent::Axis(axis_event) if sync_gamepad_axes => {
+                        if let Ok(mut gamepad) = input_writers.gamepads.get_mut(axis_event.entity) {
+                            gamepad.analog_mut().set(axis_event.axis, axis_event.value);
+                        }
+                    }
+                    // Bevy only adds and removes the `Gamepad` component in response to connection events,
+                    // which are sent alongside `GamepadEvent::Connection`
+                    GamepadEvent::Connection(connection_event)
+                        if input_writers
+                            .commands
+                            .get_entity(connection_event.gamepad)
+                            .is_some() =>
+                    {
+                        input_writers
+                            .gamepad_connection
+                            .send(connection_event.clone());
+                    }
+                    _ => (),
+                }
                 input_writers.gamepad.send(e);
             }
+            GamepadRumble(e) => {
+                input_writers
+                    .gamepad_rumble
+                    .send(bevy::input::gamepad::GamepadRumbleRequest::from(e));
+            }
+            // Pointer events must also trigger observers on their target
+            #[cfg(feature = "picking")]
+            Pointer(e) => {
+                input_writers
+                    .commands
+                    .queue(move |world: &mut World| e.replay(world));
+            }
+            // Pointer events can only be replayed with the `picking` feature
+            #[cfg(not(feature = "picking"))]
+            Pointer(_) => (),
+            // Idle markers only anchor the timing of the recording
+            IdleMarker => (),
+            // Snapshots from the same frame are summed, like Bevy does for mouse events
+            AccumulatedMouseMotion(e) => {
+                if let Some(replayed) = input_writers.replayed_accumulated_mouse.as_deref_mut() {
+                    let motion = replayed.motion.get_or_insert_default();
+                    motion.delta += e.delta;
+                }
+            }
+            AccumulatedMouseScroll(e) => {
+                if let Some(replayed) = input_writers.replayed_accumulated_mouse.as_deref_mut() {
+                    let delta = replayed
+                        .scroll
+                        .map(|scroll| scroll.delta)
+                        .unwrap_or_default();
+                    replayed.scroll = Some(bevy::input::mouse::AccumulatedMouseScroll {
+                        unit: e.unit,
+                        delta: delta + e.delta,
+                    });
+                }
+            }
+            WindowMoved(e) => {
+                if let Ok(mut window) = input_writers.windows.get_mut(e.window) {
+                    window.position = WindowPosition::At(e.position);
+                }
+                input_writers.window_moved.send(e);
+            }
             AppExit => {
                 input_writers.app_exit.send_default();
             }
@@ -338,24 +1655,57 @@ fn send_playback_events(
 }
 
 /// Reads the stored file paths from the [`PlaybackFilePath`] location (if any)
+#[cfg(feature = "file")]
 pub fn deserialize_timestamped_inputs(
     playback_path: &PlaybackFilePath,
 ) -> Option<Result<TimestampedInputs, TimestampedInputsError>> {
     playback_path.path().as_ref().map(|file_path| {
         let file = File::open(file_path).map_err(TimestampedInputsError::Fs)?;
-        from_reader(file).map_err(TimestampedInputsError::Ron)
+        read_recording(file)
+    })
+}
+
+/// Reads the stored file paths from the [`PlaybackFilePath`] location (if any), skipping any events this build does not recognize.
+///
+/// Recordings made with newer versions of this crate may contain kinds of events that this build does not know about.
+/// Rather than failing to load the whole file, these events are dropped (logging how many were dropped),
+/// so that the rest of the recording can still be played back.
+/// Recordings with an incompatible format version are loaded in the same way, with a warning.
+#[cfg(feature = "file")]
+pub fn deserialize_timestamped_inputs_lenient(
+    playback_path: &PlaybackFilePath,
+) -> Option<Result<TimestampedInputs, TimestampedInputsError>> {
+    playback_path.path().as_ref().map(|file_path| {
+        let file = File::open(file_path).map_err(TimestampedInputsError::Fs)?;
+        let lenient_inputs: LenientTimestampedInputs =
+            from_reader(file).map_err(TimestampedInputsError::Ron)?;
+        let version = lenient_inputs.header.version;
+        if !is_compatible(version) {
+            warn!("{file_path:?} was saved with the newer recording format version {version}, and may not play back correctly");
+        }
+        let (timestamped_inputs, dropped) = lenient_inputs.into_timestamped_inputs();
+        if dropped > 0 {
+            warn!("Dropped {dropped} unrecognized events while loading {file_path:?}");
+        }
+        Ok(timestamped_inputs)
     })
 }
 
 /// An error type that wraps the possible error variants when deserializing `TimestampedInputs` from a file.
+#[cfg(feature = "file")]
 #[derive(Debug)]
 pub enum TimestampedInputsError {
     /// The error case where the filesystem failed to open the desired file path.
     Fs(std::io::Error),
     /// The error case where the content at the provided filepath did not have valid RON content.
     Ron(ron::de::SpannedError),
+    /// The error case where loading the file at the provided path failed for one of the other reasons.
+    File(PathBuf, Box<TimestampedInputsError>),
+    /// The error case where the recording was saved with a newer [`RECORDING_FORMAT_VERSION`](crate::timestamped_input::RECORDING_FORMAT_VERSION) than this build can read.
+    IncompatibleVersion(u32),
 }
 
+#[cfg(feature = "file")]
 impl std::fmt::Display for TimestampedInputsError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
@@ -363,19 +1713,243 @@ impl std::fmt::Display for TimestampedInputsError {
             TimestampedInputsError::Ron(_error) => {
                 write!(f, "the provided file did not have valid RON-formatted data")
             }
+            TimestampedInputsError::File(path, error) => {
+                write!(f, "could not load {}: {error}", path.display())
+            }
+            TimestampedInputsError::IncompatibleVersion(version) => write!(
+                f,
+                "the recording has format version {version}, but only versions up to {} can be read",
+                crate::timestamped_input::RECORDING_FORMAT_VERSION
+            ),
         }
     }
 }
 
+#[cfg(feature = "file")]
 impl std::error::Error for TimestampedInputsError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match *self {
             TimestampedInputsError::Fs(ref error) => Some(error),
             TimestampedInputsError::Ron(ref error) => Some(error),
+            TimestampedInputsError::File(_, ref error) => Some(error.as_ref()),
+            TimestampedInputsError::IncompatibleVersion(_) => None,
         }
     }
 }
 
+/// Replays a recording in an [`App`], taking a snapshot of its [`World`] at each of the requested frames.
+///
+/// Inputs are played back using [`PlaybackStrategy::FrameCount`],
+/// and each snapshot is taken at the end of the update in which the app's [`FrameCount`] matched the requested frame.
+/// This turns recordings into golden-state fixtures for regression tests.
+pub struct ReplayWithSnapshots<S, F: Fn(&World) -> S> {
+    frames: Vec<FrameCount>,
+    snapshot: F,
+}
+
+impl<S, F: Fn(&World) -> S> ReplayWithSnapshots<S, F> {
+    /// Creates a new [`ReplayWithSnapshots`], which calls `snapshot` at each of the provided `frames`.
+    pub fn new(frames: impl IntoIterator<Item = FrameCount>, snapshot: F) -> Self {
+        let mut frames: Vec<FrameCount> = frames.into_iter().collect();
+        frames.sort_by_key(|frame| frame.0);
+        frames.dedup();
+
+        ReplayWithSnapshots { frames, snapshot }
+    }
+
+    /// Plays back the `timestamped_inputs` in the `app`, updating it until every snapshot has been taken.
+    ///
+    /// Snapshots are returned in order of their frame.
+    /// The [`InputPlaybackPlugin`] must have been added to the `app`.
+    pub fn run(
+        &self,
+        app: &mut App,
+        timestamped_inputs: TimestampedInputs,
+    ) -> Vec<(FrameCount, S)> {
+        app.world_mut().trigger(BeginInputPlayback {
+            source: Some(InputPlaybackSource::from_inputs(timestamped_inputs)),
+            playback_strategy: PlaybackStrategy::FrameCount,
+            ..Default::default()
+        });
+        app.world_mut().flush();
+
+        let mut snapshots = Vec::with_capacity(self.frames.len());
+        for &frame in self.frames.iter() {
+            while app.world().resource::<FrameCount>().0 <= frame.0 {
+                app.update();
+            }
+            snapshots.push((frame, (self.snapshot)(app.world())));
+        }
+        snapshots
+    }
+}
+
+/// If this resource exists, a [`PlaybackCheckpoint`] is saved to the stored `path` every `interval` frames during playback.
+///
+/// Playback can then be resumed from this checkpoint after a restart, see [`BeginInputPlayback::resume`].
+#[cfg(feature = "file")]
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct PersistPlaybackProgress {
+    /// The path that checkpoints are saved to.
+    pub path: PathBuf,
+    /// The number of frames between saved checkpoints.
+    ///
+    /// Each checkpoint hashes the whole recording, so saving every frame is expensive for long recordings.
+    pub interval: u32,
+}
+
+#[cfg(feature = "file")]
+impl PersistPlaybackProgress {
+    /// Creates a new [`PersistPlaybackProgress`], which saves a checkpoint to the provided `path` once per second at 60 FPS.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        PersistPlaybackProgress {
+            path: path.into(),
+            interval: 60,
+        }
+    }
+}
+
+/// An offset that is added to the app's time since startup during [`PlaybackStrategy::Time`] playback.
+///
+/// This is inserted when playback resumes from a [`PlaybackCheckpoint`] after a restart,
+/// so that the remaining events are played back relative to where playback left off, rather than relative to the new app's startup.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PlaybackTimeOffset(pub Duration);
+
+/// A snapshot of how far playback has progressed through a recording, which can be saved to disk.
+#[cfg(feature = "file")]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlaybackCheckpoint {
+    /// The cursor of the [`TimestampedInputs`] being played back.
+    pub cursor: usize,
+    /// A hash of the events in the [`TimestampedInputs`] being played back, see [`PlaybackCheckpoint::hash_recording`].
+    ///
+    /// Used to detect whether the checkpoint belongs to a different recording.
+    pub recording_hash: u64,
+    /// The [`PlaybackProgress::elapsed_time`].
+    pub elapsed_time: Duration,
+    /// The [`PlaybackProgress::elapsed_frames`].
+    pub elapsed_frames: FrameCount,
+    /// The playback time that had been reached under [`PlaybackStrategy::Time`], including any [`PlaybackTimeOffset`].
+    pub time_since_startup: Duration,
+}
+
+#[cfg(feature = "file")]
+impl PlaybackCheckpoint {
+    /// Creates a checkpoint of the current playback progress, `time_since_startup` into playback.
+    pub fn new(
+        timestamped_inputs: &TimestampedInputs,
+        playback_progress: &PlaybackProgress,
+        time_since_startup: Duration,
+    ) -> Self {
+        PlaybackCheckpoint {
+            cursor: timestamped_inputs.cursor,
+            recording_hash: PlaybackCheckpoint::hash_recording(timestamped_inputs),
+            elapsed_time: playback_progress.elapsed_time,
+            elapsed_frames: playback_progress.elapsed_frames,
+            time_since_startup,
+        }
+    }
+
+    /// Hashes the serialized events of the provided `timestamped_inputs`, ignoring their cursor.
+    ///
+    /// Hashes are only stable between builds made with the same version of Rust.
+    pub fn hash_recording(timestamped_inputs: &TimestampedInputs) -> u64 {
+        let mut writer = HashWriter(DefaultHasher::new());
+        // Writing into a hasher cannot fail, and all events can be serialized
+        let _ = ron::ser::to_writer(&mut writer, &timestamped_inputs.events);
+        writer.0.finish()
+    }
+
+    /// Saves this checkpoint to the provided `path`.
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let checkpoint = ron::ser::to_string(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, checkpoint)
+    }
+
+    /// Loads a checkpoint from the provided `path`.
+    pub fn load(path: &Path) -> Result<Self, TimestampedInputsError> {
+        let file = File::open(path).map_err(TimestampedInputsError::Fs)?;
+        from_reader(file).map_err(TimestampedInputsError::Ron)
+    }
+}
+
+/// Feeds everything written to it into a [`Hasher`], see [`PlaybackCheckpoint::hash_recording`].
+#[cfg(feature = "file")]
+struct HashWriter<H: Hasher>(H);
+
+#[cfg(feature = "file")]
+impl<H: Hasher> std::io::Write for HashWriter<H> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.write(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Saves a [`PlaybackCheckpoint`] to the path stored in [`PersistPlaybackProgress`] every [`PersistPlaybackProgress::interval`] frames.
+#[cfg(feature = "file")]
+pub fn persist_playback_progress(
+    persist_playback_progress: Res<PersistPlaybackProgress>,
+    timestamped_input: Res<TimestampedInputs>,
+    playback_progress: Res<PlaybackProgress>,
+    time_offset: Option<Res<PlaybackTimeOffset>>,
+    time: Res<Time>,
+    mut frames_since_save: Local<u32>,
+) {
+    *frames_since_save += 1;
+    if *frames_since_save < persist_playback_progress.interval {
+        return;
+    }
+    *frames_since_save = 0;
+
+    let time_offset = time_offset.map(|offset| offset.0).unwrap_or_default();
+    let checkpoint = PlaybackCheckpoint::new(
+        &timestamped_input,
+        &playback_progress,
+        time.elapsed() + time_offset,
+    );
+    if let Err(error) = checkpoint.save(&persist_playback_progress.path) {
+        warn!("Could not save playback progress: {error}");
+    }
+}
+
+/// Restores the [`PlaybackCheckpoint`] saved at the path stored in [`PersistPlaybackProgress`], if any.
+#[cfg(feature = "file")]
+fn resume_from_checkpoint(world: &mut World) {
+    let Some(persist_playback_progress) = world.get_resource::<PersistPlaybackProgress>() else {
+        warn!("Playback cannot be resumed without a PersistPlaybackProgress resource");
+        return;
+    };
+    // If no checkpoint has been saved yet, playback simply starts from the beginning
+    let Ok(checkpoint) = PlaybackCheckpoint::load(&persist_playback_progress.path) else {
+        return;
+    };
+    let Some(mut timestamped_input) = world.get_resource_mut::<TimestampedInputs>() else {
+        return;
+    };
+    if PlaybackCheckpoint::hash_recording(&timestamped_input) != checkpoint.recording_hash {
+        warn!("The saved playback checkpoint belongs to a different recording, and was ignored");
+        return;
+    }
+
+    timestamped_input.cursor = checkpoint.cursor;
+    let mut playback_progress = world.resource_mut::<PlaybackProgress>();
+    playback_progress.elapsed_time = checkpoint.elapsed_time;
+    playback_progress.elapsed_frames = checkpoint.elapsed_frames;
+
+    // Continue from the playback time that was reached before the restart
+    if world.get_resource::<PlaybackStrategy>() == Some(&PlaybackStrategy::Time) {
+        let time_offset = checkpoint
+            .time_since_startup
+            .saturating_sub(world.resource::<Time>().elapsed());
+        world.insert_resource(PlaybackTimeOffset(time_offset));
+    }
+}
+
 /// How far through the current cycle of input playback we've gotten.
 ///
 /// The `initial_time` and `initial_frame` are stored to be able to compute
@@ -418,6 +1992,32 @@ impl PlaybackProgress {
         self.current_frame(start)
     }
 
+    /// Gets the number of frames left to play back in the range of the provided `playback_strategy`.
+    ///
+    /// Returns `None` unless the strategy is [`PlaybackStrategy::FrameRangeOnce`] or [`PlaybackStrategy::FrameRangeLoop`].
+    pub fn remaining_frames(&self, playback_strategy: PlaybackStrategy) -> Option<u32> {
+        match playback_strategy {
+            PlaybackStrategy::FrameRangeOnce(start, end)
+            | PlaybackStrategy::FrameRangeLoop(start, end) => {
+                Some(end.0.saturating_sub(self.current_frame(start).0))
+            }
+            _ => None,
+        }
+    }
+
+    /// Gets the time left to play back in the range of the provided `playback_strategy`.
+    ///
+    /// Returns `None` unless the strategy is [`PlaybackStrategy::TimeRangeOnce`] or [`PlaybackStrategy::TimeRangeLoop`].
+    pub fn remaining_time(&self, playback_strategy: PlaybackStrategy) -> Option<Duration> {
+        match playback_strategy {
+            PlaybackStrategy::TimeRangeOnce(start, end)
+            | PlaybackStrategy::TimeRangeLoop(start, end) => {
+                Some(end.saturating_sub(self.current_time(start)))
+            }
+            _ => None,
+        }
+    }
+
     /// Get the start of the next time window to play back.
     ///
     /// This also records that a `delta` of time has elapsed.
@@ -453,6 +2053,64 @@ mod tests {
         assert_eq!(progress.elapsed_time, delta);
     }
 
+    #[test]
+    fn display_playback_strategy() {
+        assert_eq!(PlaybackStrategy::Time.to_string(), "Playing by time");
+        assert_eq!(PlaybackStrategy::Paused.to_string(), "Paused");
+        assert_eq!(
+            PlaybackStrategy::Immediate.to_string(),
+            "Playing all events at once"
+        );
+        assert_eq!(
+            PlaybackStrategy::RecordedFrameRate.to_string(),
+            "Playing at the recorded frame rate"
+        );
+        assert_eq!(
+            PlaybackStrategy::FrameRangeLoop(FrameCount(100), FrameCount(500)).to_string(),
+            "Looping frames 100–500"
+        );
+        assert_eq!(
+            PlaybackStrategy::FrameRangeOnce(FrameCount(2), FrameCount(5)).to_string(),
+            "Playing frames 2–5 once"
+        );
+        assert_eq!(
+            PlaybackStrategy::TimeRangeOnce(Duration::from_millis(1500), Duration::from_secs(3))
+                .to_string(),
+            "Playing 1.5s–3s once"
+        );
+    }
+
+    #[test]
+    fn remaining_frames() {
+        let mut progress = PlaybackProgress::default();
+        let strategy = PlaybackStrategy::FrameRangeOnce(FrameCount(2), FrameCount(5));
+        assert_eq!(progress.remaining_frames(strategy), Some(3));
+
+        // Midway through the range
+        progress.next_frame(FrameCount(2));
+        assert_eq!(progress.remaining_frames(strategy), Some(2));
+
+        assert_eq!(progress.remaining_time(strategy), None);
+        assert_eq!(progress.remaining_frames(PlaybackStrategy::Time), None);
+    }
+
+    #[test]
+    fn remaining_time() {
+        let mut progress = PlaybackProgress::default();
+        let strategy =
+            PlaybackStrategy::TimeRangeLoop(Duration::from_secs(1), Duration::from_secs(4));
+
+        progress.next_time(Duration::from_secs(2), Duration::from_secs(1));
+        assert_eq!(
+            progress.remaining_time(strategy),
+            Some(Duration::from_secs(1))
+        );
+
+        progress.next_time(Duration::from_secs(2), Duration::from_secs(1));
+        assert_eq!(progress.remaining_time(strategy), Some(Duration::ZERO));
+        assert_eq!(progress.remaining_frames(strategy), None);
+    }
+
     #[test]
     fn current_frame() {
         let mut progress = PlaybackProgress::default();
@@ -3,7 +3,13 @@
 #![warn(clippy::doc_markdown)]
 #![doc = include_str!("../README.md")]
 
+#[cfg(feature = "actions")]
+pub mod actions;
 pub mod input_capture;
 pub mod input_playback;
+pub mod picking;
+#[cfg(feature = "file")]
 pub mod serde;
+#[cfg(feature = "test-support")]
+pub mod test_support;
 pub mod timestamped_input;
@@ -0,0 +1,281 @@
+//! Captures and replays [`bevy::picking`] pointer events.
+//!
+//! Picking hit-tests depend on the layout of the app, so replaying raw mouse input does not always reproduce the same interactions.
+//! Instead, the resulting [`Pointer`] events can be captured and replayed directly.
+//! Only pointers that target a window are captured.
+//!
+//! The recorded [`PointerEvent`]s are always available, so that recordings containing them can be loaded by any build.
+//! Capturing and replaying them requires the `picking` feature.
+
+use bevy::ecs::prelude::*;
+use bevy::math::{Vec2, Vec3};
+#[cfg(feature = "picking")]
+use bevy::picking::backend::HitData;
+#[cfg(feature = "picking")]
+use bevy::picking::events::{Click, Down, Pointer, Up};
+#[cfg(feature = "picking")]
+use bevy::picking::pointer::{Location, PointerButton, PointerId};
+#[cfg(feature = "picking")]
+use bevy::render::camera::NormalizedRenderTarget;
+#[cfg(feature = "picking")]
+use bevy::time::Time;
+use bevy::utils::Duration;
+#[cfg(feature = "picking")]
+use bevy::window::WindowRef;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "picking")]
+use crate::input_capture::{
+    CaptureClock, CaptureFilter, CaptureState, InputModesCaptured, PreciseTimestamps,
+};
+use crate::timestamped_input::InputEvent;
+#[cfg(feature = "picking")]
+use crate::timestamped_input::TimestampedInputs;
+
+/// If this resource exists, [`Pointer`] events are captured by [`capture_pointer_events`].
+///
+/// This is inserted by [`BeginInputCapture::capture_pointer_events`](crate::input_capture::BeginInputCapture::capture_pointer_events).
+#[cfg(feature = "picking")]
+#[derive(Debug, Resource, Default)]
+pub struct CapturePointerEvents;
+
+/// A serializable copy of a [`Pointer`] event, as stored in [`InputEvent::Pointer`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct PointerEvent {
+    /// The kind of pointer event that was sent.
+    pub kind: PointerEventKind,
+    /// The entity that the event targeted, see [`Pointer::target`].
+    pub target: Entity,
+    /// The pointer that sent the event, see [`Pointer::pointer_id`].
+    pub pointer_id: RecordedPointerId,
+    /// The window that the pointer was in.
+    pub window: Entity,
+    /// The position of the pointer in the `window`.
+    pub position: Vec2,
+    /// The pointer button that was pressed or released.
+    pub button: RecordedPointerButton,
+    /// The camera used to detect the hit, see [`HitData::camera`].
+    pub camera: Entity,
+    /// The depth of the hit, see [`HitData::depth`].
+    pub depth: f32,
+    /// The position of the hit in the world, see [`HitData::position`].
+    pub hit_position: Option<Vec3>,
+    /// The normal of the hit, see [`HitData::normal`].
+    pub hit_normal: Option<Vec3>,
+}
+
+/// The kinds of [`Pointer`] events that can be captured.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PointerEventKind {
+    /// See [`Down`]
+    Down,
+    /// See [`Up`]
+    Up,
+    /// See [`Click`]
+    Click {
+        /// The duration between the press and release of the pointer button
+        duration: Duration,
+    },
+}
+
+/// A serializable copy of a [`PointerId`].
+///
+/// [`PointerId::Custom`] pointers are not captured.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum RecordedPointerId {
+    /// See [`PointerId::Mouse`]
+    Mouse,
+    /// See [`PointerId::Touch`]
+    Touch(u64),
+}
+
+/// A serializable copy of a [`PointerButton`].
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum RecordedPointerButton {
+    Primary,
+    Secondary,
+    Middle,
+}
+
+#[cfg(feature = "picking")]
+impl From<PointerButton> for RecordedPointerButton {
+    fn from(button: PointerButton) -> Self {
+        match button {
+            PointerButton::Primary => RecordedPointerButton::Primary,
+            PointerButton::Secondary => RecordedPointerButton::Secondary,
+            PointerButton::Middle => RecordedPointerButton::Middle,
+        }
+    }
+}
+
+#[cfg(feature = "picking")]
+impl From<RecordedPointerButton> for PointerButton {
+    fn from(button: RecordedPointerButton) -> Self {
+        match button {
+            RecordedPointerButton::Primary => PointerButton::Primary,
+            RecordedPointerButton::Secondary => PointerButton::Secondary,
+            RecordedPointerButton::Middle => PointerButton::Middle,
+        }
+    }
+}
+
+#[cfg(feature = "picking")]
+impl PointerEvent {
+    /// Copies the data of a [`Pointer`] event.
+    ///
+    /// Returns [`None`] if the pointer is a [`PointerId::Custom`] pointer, or does not target a window.
+    pub(crate) fn new<E: std::fmt::Debug + Clone + bevy::reflect::Reflect>(
+        kind: PointerEventKind,
+        pointer: &Pointer<E>,
+        button: PointerButton,
+        hit: &HitData,
+    ) -> Option<Self> {
+        let pointer_id = match pointer.pointer_id {
+            PointerId::Mouse => RecordedPointerId::Mouse,
+            PointerId::Touch(id) => RecordedPointerId::Touch(id),
+            PointerId::Custom(_) => return None,
+        };
+        let NormalizedRenderTarget::Window(window) = &pointer.pointer_location.target else {
+            return None;
+        };
+
+        Some(PointerEvent {
+            kind,
+            target: pointer.target,
+            pointer_id,
+            window: window.entity(),
+            position: pointer.pointer_location.position,
+            button: button.into(),
+            camera: hit.camera,
+            depth: hit.depth,
+            hit_position: hit.position,
+            hit_normal: hit.normal,
+        })
+    }
+
+    /// Sends this event as a [`Pointer`] event, triggering any observers on its `target` as well.
+    pub fn replay(&self, world: &mut World) {
+        let pointer_id = match self.pointer_id {
+            RecordedPointerId::Mouse => PointerId::Mouse,
+            RecordedPointerId::Touch(id) => PointerId::Touch(id),
+        };
+        let Some(window) = WindowRef::Entity(self.window).normalize(None) else {
+            return;
+        };
+        let location = Location {
+            target: NormalizedRenderTarget::Window(window),
+            position: self.position,
+        };
+        let button = PointerButton::from(self.button);
+        let hit = HitData {
+            camera: self.camera,
+            depth: self.depth,
+            position: self.hit_position,
+            normal: self.hit_normal,
+        };
+
+        match self.kind {
+            PointerEventKind::Down => send_pointer(
+                world,
+                self.target,
+                pointer_id,
+                location,
+                Down { button, hit },
+            ),
+            PointerEventKind::Up => {
+                send_pointer(world, self.target, pointer_id, location, Up { button, hit })
+            }
+            PointerEventKind::Click { duration } => send_pointer(
+                world,
+                self.target,
+                pointer_id,
+                location,
+                Click {
+                    button,
+                    hit,
+                    duration,
+                },
+            ),
+        }
+    }
+}
+
+/// Triggers a [`Pointer`] event on its `target`, and sends it to the corresponding [`Events`] resource, like `bevy::picking` does.
+#[cfg(feature = "picking")]
+fn send_pointer<E: std::fmt::Debug + Clone + bevy::reflect::Reflect>(
+    world: &mut World,
+    target: Entity,
+    pointer_id: PointerId,
+    location: Location,
+    event: E,
+) {
+    let pointer = Pointer::new(target, pointer_id, location, event);
+    world.trigger_targets(pointer.clone(), target);
+    world.send_event(pointer);
+}
+
+impl From<PointerEvent> for InputEvent {
+    fn from(event: PointerEvent) -> Self {
+        InputEvent::Pointer(event)
+    }
+}
+
+/// Captures [`Pointer`] presses, releases and clicks, see [`CapturePointerEvents`].
+///
+/// These are captured as mouse button inputs, under the [`InputModesCaptured`].
+#[cfg(feature = "picking")]
+#[allow(clippy::too_many_arguments)]
+pub fn capture_pointer_events(
+    mut down_events: EventReader<Pointer<Down>>,
+    mut up_events: EventReader<Pointer<Up>>,
+    mut click_events: EventReader<Pointer<Click>>,
+    mut timestamped_input: ResMut<TimestampedInputs>,
+    input_modes_captured: Option<Res<InputModesCaptured>>,
+    capture_filter: Option<Res<CaptureFilter>>,
+    capture_state: Res<CaptureState>,
+    capture_clock: CaptureClock,
+    time: Res<Time>,
+    precise_timestamps: Option<Res<PreciseTimestamps>>,
+) {
+    let Some(input_modes_captured) = input_modes_captured.filter(|_| capture_state.is_capturing())
+    else {
+        down_events.clear();
+        up_events.clear();
+        click_events.clear();
+        return;
+    };
+
+    let down = down_events
+        .read()
+        .filter_map(|e| PointerEvent::new(PointerEventKind::Down, e, e.button, &e.hit));
+    let up = up_events
+        .read()
+        .filter_map(|e| PointerEvent::new(PointerEventKind::Up, e, e.button, &e.hit));
+    let click = click_events.read().filter_map(|e| {
+        let kind = PointerEventKind::Click {
+            duration: e.duration,
+        };
+        PointerEvent::new(kind, e, e.button, &e.hit)
+    });
+
+    let first_new_event = timestamped_input.len();
+    timestamped_input.send_multiple(
+        capture_clock.frame(),
+        time.elapsed(),
+        down.chain(up)
+            .chain(click)
+            .map(InputEvent::from)
+            .filter(|event| {
+                input_modes_captured.captures(event)
+                    && capture_filter
+                        .as_deref()
+                        .map(|capture_filter| (capture_filter.0)(event))
+                        .unwrap_or(true)
+            }),
+    );
+
+    if let Some(precise_timestamps) = precise_timestamps {
+        precise_timestamps.stamp(&mut timestamped_input.events[first_new_event..]);
+    }
+}
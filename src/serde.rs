@@ -1,6 +1,18 @@
 //! Serialization and deserialization of [`TimestampedInputs`](crate::timestamped_input::TimestampedInputs) data
-use bevy::ecs::prelude::Resource;
-use std::path::PathBuf;
+use bevy::core::FrameCount;
+use bevy::ecs::prelude::{Commands, ResMut, Resource};
+use bevy::log::warn;
+use bevy::utils::Duration;
+use serde::de::{self, Deserializer, EnumAccess, IgnoredAny, VariantAccess, Visitor};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use crate::input_playback::{PlaybackStrategy, TimestampedInputsError};
+use crate::timestamped_input::{
+    is_compatible, InputEvent, Modifiers, RecordingHeader, TimestampedInputEvent, TimestampedInputs,
+};
 /// The file path where captured events will be saved to and read from.
 ///
 /// Currently, only `.ron` serialization / deserialization is supported.
@@ -14,13 +26,13 @@ pub struct PlaybackFilePath {
 }
 
 impl PlaybackFilePath {
-    /// Creates a new [`PlaybackFilePath`] from a quoted string.
+    /// Creates a new [`PlaybackFilePath`] from any path-like value, such as a `&str`, [`String`] or [`PathBuf`].
     ///
     /// # Panics
     ///
     /// Only `.ron` files are supported, and so this method will panic if a path with an incompatible extension is provided.
-    pub fn new(path_str: &str) -> Self {
-        let path: PathBuf = path_str.into();
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let path: PathBuf = path.into();
         assert_eq!(path.extension().unwrap(), "ron");
 
         PlaybackFilePath { path: Some(path) }
@@ -46,3 +58,665 @@ impl PlaybackFilePath {
         self.path = path;
     }
 }
+
+/// Reads a recording that was saved as a whole, such as when input capture ends.
+///
+/// If the recording's [`RecordingHeader`] has a format version that this build cannot read (see [`is_compatible`]),
+/// a [`TimestampedInputsError::IncompatibleVersion`] is returned instead, even if the rest of the recording could not be parsed.
+pub fn read_recording(mut reader: impl Read) -> Result<TimestampedInputs, TimestampedInputsError> {
+    let mut recording = String::new();
+    reader
+        .read_to_string(&mut recording)
+        .map_err(TimestampedInputsError::Fs)?;
+
+    match ron::de::from_str::<TimestampedInputs>(&recording) {
+        Ok(timestamped_inputs) if is_compatible(timestamped_inputs.header.version) => {
+            Ok(timestamped_inputs)
+        }
+        Ok(timestamped_inputs) => Err(TimestampedInputsError::IncompatibleVersion(
+            timestamped_inputs.header.version,
+        )),
+        // Newer recordings may contain data that cannot be parsed, so only their header is checked
+        Err(error) => match ron::de::from_str::<RecordingHeaderOnly>(&recording) {
+            Ok(RecordingHeaderOnly { header }) if !is_compatible(header.version) => {
+                Err(TimestampedInputsError::IncompatibleVersion(header.version))
+            }
+            _ => Err(TimestampedInputsError::Ron(error)),
+        },
+    }
+}
+
+/// The [`RecordingHeader`] of a recording, ignoring all of its other data.
+#[derive(Debug, Deserialize)]
+struct RecordingHeaderOnly {
+    #[serde(default = "RecordingHeader::unversioned")]
+    header: RecordingHeader,
+}
+
+/// [`TimestampedInputs`] data, deserialized without failing on unrecognized kinds of events.
+///
+/// Used by [`deserialize_timestamped_inputs_lenient`](crate::input_playback::deserialize_timestamped_inputs_lenient).
+#[derive(Debug, Deserialize)]
+pub(crate) struct LenientTimestampedInputs {
+    #[serde(default = "RecordingHeader::unversioned")]
+    pub(crate) header: RecordingHeader,
+    events: Vec<LenientTimestampedInputEvent>,
+}
+
+impl LenientTimestampedInputs {
+    /// Drops any unrecognized events, returning the remaining inputs and the number of events dropped.
+    pub(crate) fn into_timestamped_inputs(self) -> (TimestampedInputs, usize) {
+        let total = self.events.len();
+        let events: Vec<TimestampedInputEvent> = self
+            .events
+            .into_iter()
+            .filter_map(|event| {
+                Some(TimestampedInputEvent {
+                    frame: event.frame,
+                    time_since_startup: event.time_since_startup,
+                    input_event: event.input_event.0?,
+                    precise_time: event.precise_time,
+                    modifiers: event.modifiers,
+                })
+            })
+            .collect();
+        let dropped = total - events.len();
+
+        (
+            TimestampedInputs {
+                header: self.header,
+                events,
+                ..Default::default()
+            },
+            dropped,
+        )
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct LenientTimestampedInputEvent {
+    frame: FrameCount,
+    time_since_startup: Duration,
+    input_event: LenientInputEvent,
+    #[serde(default)]
+    precise_time: Option<Duration>,
+    #[serde(default)]
+    modifiers: Option<Modifiers>,
+}
+
+/// An [`InputEvent`], or `None` if the variant was not recognized.
+///
+/// Unrecognized variants can only be skipped if they carry data, like every input event type does.
+#[derive(Debug)]
+struct LenientInputEvent(Option<InputEvent>);
+
+impl<'de> Deserialize<'de> for LenientInputEvent {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        const VARIANTS: &[&str] = &[
+            "Keyboard",
+            "MouseButton",
+            "MouseWheel",
+            "CursorMoved",
+            "Gamepad",
+            "AppExit",
+            "IdleMarker",
+            "AccumulatedMouseMotion",
+            "AccumulatedMouseScroll",
+            "GamepadRumble",
+            "Pointer",
+            "WindowMoved",
+            "Ime",
+        ];
+
+        struct LenientInputEventVisitor;
+
+        impl<'de> Visitor<'de> for LenientInputEventVisitor {
+            type Value = LenientInputEvent;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(formatter, "an InputEvent variant")
+            }
+
+            fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+                let (VariantName(name), variant) = data.variant()?;
+                let input_event = match name.as_str() {
+                    "Keyboard" => Some(InputEvent::Keyboard(variant.newtype_variant()?)),
+                    "MouseButton" => Some(InputEvent::MouseButton(variant.newtype_variant()?)),
+                    "MouseWheel" => Some(InputEvent::MouseWheel(variant.newtype_variant()?)),
+                    "CursorMoved" => Some(InputEvent::CursorMoved(variant.newtype_variant()?)),
+                    "Gamepad" => Some(InputEvent::Gamepad(variant.newtype_variant()?)),
+                    "AppExit" => variant.unit_variant().map(|_| Some(InputEvent::AppExit))?,
+                    "IdleMarker" => variant
+                        .unit_variant()
+                        .map(|_| Some(InputEvent::IdleMarker))?,
+                    "AccumulatedMouseMotion" => Some(InputEvent::AccumulatedMouseMotion(
+                        variant.newtype_variant()?,
+                    )),
+                    "AccumulatedMouseScroll" => Some(InputEvent::AccumulatedMouseScroll(
+                        variant.newtype_variant()?,
+                    )),
+                    "GamepadRumble" => Some(InputEvent::GamepadRumble(variant.newtype_variant()?)),
+                    "Pointer" => Some(InputEvent::Pointer(variant.newtype_variant()?)),
+                    "WindowMoved" => Some(InputEvent::WindowMoved(variant.newtype_variant()?)),
+                    "Ime" => Some(InputEvent::Ime(variant.newtype_variant()?)),
+                    _ => variant.newtype_variant::<IgnoredAny>().map(|_| None)?,
+                };
+                Ok(LenientInputEvent(input_event))
+            }
+        }
+
+        deserializer.deserialize_enum("InputEvent", VARIANTS, LenientInputEventVisitor)
+    }
+}
+
+/// The name of an enum variant, which may not be known ahead of time.
+struct VariantName(String);
+
+impl<'de> Deserialize<'de> for VariantName {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct VariantNameVisitor;
+
+        impl Visitor<'_> for VariantNameVisitor {
+            type Value = VariantName;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(formatter, "a variant identifier")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(VariantName(value.to_string()))
+            }
+        }
+
+        deserializer.deserialize_identifier(VariantNameVisitor)
+    }
+}
+
+/// Appends `events` to the streamed recording at `path`, with one RON-serialized event per line.
+///
+/// If `truncate` is `true`, any existing file is replaced rather than appended to.
+/// If `sync` is `true`, the file is flushed all the way to disk before returning.
+///
+/// Streamed recordings can be read back with [`read_streamed_recording`].
+pub fn append_to_streamed_recording(
+    events: &[TimestampedInputEvent],
+    path: &Path,
+    truncate: bool,
+    sync: bool,
+) -> std::io::Result<()> {
+    let file = std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(!truncate)
+        .truncate(truncate)
+        .open(path)?;
+    let mut writer = BufWriter::new(file);
+    for event in events {
+        let line = ron::ser::to_string(event).map_err(std::io::Error::other)?;
+        writeln!(writer, "{line}")?;
+    }
+    writer.flush()?;
+
+    if sync {
+        writer.get_ref().sync_all()?;
+    }
+    Ok(())
+}
+
+/// Reads a recording written by [`append_to_streamed_recording`].
+///
+/// Every complete line is loaded, so a recording that is still being streamed can be read between flushes.
+pub fn read_streamed_recording(
+    path: impl AsRef<Path>,
+) -> Result<TimestampedInputs, TimestampedInputsError> {
+    let file = File::open(path).map_err(TimestampedInputsError::Fs)?;
+    let mut timestamped_inputs = TimestampedInputs::default();
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(TimestampedInputsError::Fs)?;
+        if line.is_empty() {
+            continue;
+        }
+        let event = ron::de::from_str(&line).map_err(TimestampedInputsError::Ron)?;
+        timestamped_inputs.events.push(event);
+    }
+    Ok(timestamped_inputs)
+}
+
+/// Plays back a recording written by [`append_to_streamed_recording`] without loading all of it into memory.
+///
+/// Insert this resource, then trigger [`BeginInputPlayback`](crate::input_playback::BeginInputPlayback)
+/// with an empty [`TimestampedInputs`] as its source.
+/// Each frame, before inputs are played back, [`stream_playback_events`] discards the events that have already been played,
+/// and reads more events from the recording until at most `chunk_size` events are buffered.
+///
+/// As played events are discarded, the buffered events no longer span the whole recording,
+/// so only strategies that move forward through the recording without measuring it are supported, see [`StreamingPlaybackSource::supports`].
+/// If any other strategy is used, a warning is logged and playback is paused.
+/// No more than `chunk_size` events can be played back in a single frame.
+#[derive(Resource)]
+pub struct StreamingPlaybackSource {
+    reader: Box<dyn BufRead + Send + Sync>,
+    chunk_size: usize,
+    exhausted: bool,
+}
+
+impl StreamingPlaybackSource {
+    /// Creates a new [`StreamingPlaybackSource`], which reads events from the `reader` and buffers at most `chunk_size` events.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    pub fn new(reader: impl BufRead + Send + Sync + 'static, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "chunk_size must be non-zero");
+
+        StreamingPlaybackSource {
+            reader: Box::new(reader),
+            chunk_size,
+            exhausted: false,
+        }
+    }
+
+    /// Opens the streamed recording at `path`, buffering at most `chunk_size` events.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    pub fn open(path: impl AsRef<Path>, chunk_size: usize) -> Result<Self, TimestampedInputsError> {
+        let file = File::open(path).map_err(TimestampedInputsError::Fs)?;
+        Ok(StreamingPlaybackSource::new(
+            BufReader::new(file),
+            chunk_size,
+        ))
+    }
+
+    /// The maximum number of events that are buffered at once.
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Has every event in the recording been read?
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    /// Can recordings be streamed under the provided `playback_strategy`?
+    ///
+    /// [`PlaybackStrategy::RecordedFrameRate`] and the range strategies measure the recording from its first event,
+    /// or return to earlier events, so they cannot be used once played events have been discarded.
+    pub fn supports(playback_strategy: PlaybackStrategy) -> bool {
+        matches!(
+            playback_strategy,
+            PlaybackStrategy::Time
+                | PlaybackStrategy::FrameCount
+                | PlaybackStrategy::Gated
+                | PlaybackStrategy::Paused
+        )
+    }
+
+    /// Discards the events of `timestamped_inputs` before its cursor, then reads events until `chunk_size` are buffered.
+    ///
+    /// The cursor of `timestamped_inputs` is reset, as the events it pointed past have been removed.
+    pub fn refill(
+        &mut self,
+        timestamped_inputs: &mut TimestampedInputs,
+    ) -> Result<(), TimestampedInputsError> {
+        let consumed = timestamped_inputs.cursor.min(timestamped_inputs.len());
+        timestamped_inputs.events.drain(..consumed);
+        timestamped_inputs.reset_cursor();
+
+        let mut line = String::new();
+        while !self.exhausted && timestamped_inputs.len() < self.chunk_size {
+            line.clear();
+            let bytes = self
+                .reader
+                .read_line(&mut line)
+                .map_err(TimestampedInputsError::Fs)?;
+            if bytes == 0 {
+                self.exhausted = true;
+                break;
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            let event = ron::de::from_str(&line).map_err(TimestampedInputsError::Ron)?;
+            timestamped_inputs.events.push(event);
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for StreamingPlaybackSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("StreamingPlaybackSource")
+            .field("chunk_size", &self.chunk_size)
+            .field("exhausted", &self.exhausted)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Refills the [`TimestampedInputs`] from the [`StreamingPlaybackSource`], see [`StreamingPlaybackSource::refill`].
+///
+/// If the recording cannot be read, a warning is logged and the [`StreamingPlaybackSource`] is removed.
+/// If the current [`PlaybackStrategy`] is not [supported](StreamingPlaybackSource::supports), a warning is logged and playback is paused instead.
+pub fn stream_playback_events(
+    mut streaming_source: ResMut<StreamingPlaybackSource>,
+    mut timestamped_input: ResMut<TimestampedInputs>,
+    playback_strategy: Option<ResMut<PlaybackStrategy>>,
+    mut commands: Commands,
+) {
+    if let Some(mut playback_strategy) = playback_strategy {
+        if !StreamingPlaybackSource::supports(*playback_strategy) {
+            warn!("{playback_strategy:?} is not supported when streaming playback events, so playback has been paused");
+            *playback_strategy = PlaybackStrategy::Paused;
+        }
+    }
+
+    if let Err(error) = streaming_source.refill(&mut timestamped_input) {
+        warn!("Could not stream playback events: {error}");
+        commands.remove_resource::<StreamingPlaybackSource>();
+    }
+}
+
+/// A checkpoint in a [`RecordingIndex`], marking where an event begins in a seekable recording.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexCheckpoint {
+    /// The [`FrameCount`] of the event at this checkpoint.
+    pub frame: FrameCount,
+    /// The time since startup of the event at this checkpoint.
+    pub time_since_startup: Duration,
+    /// The byte offset in the recording file at which the event begins.
+    pub byte_offset: u64,
+}
+
+/// An index of checkpoints into a recording written by [`write_seekable_recording`].
+///
+/// This is stored in a `.idx` sidecar file next to the recording, see [`index_path`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordingIndex {
+    /// The checkpoints, sorted by [`FrameCount`].
+    pub checkpoints: Vec<IndexCheckpoint>,
+}
+
+/// The path of the `.idx` sidecar file for the recording at `recording_path`.
+pub fn index_path(recording_path: &Path) -> PathBuf {
+    recording_path.with_extension("idx")
+}
+
+/// Writes `timestamped_inputs` to `path` as a seekable recording, with one RON-serialized event per line.
+///
+/// A [`RecordingIndex`] with a checkpoint every `checkpoint_interval` events is written to the `.idx` sidecar file,
+/// so that [`SeekableRecording`] can load a window of the recording without reading the whole file.
+///
+/// # Panics
+///
+/// Panics if `checkpoint_interval` is zero.
+pub fn write_seekable_recording(
+    timestamped_inputs: &TimestampedInputs,
+    path: &Path,
+    checkpoint_interval: usize,
+) -> std::io::Result<()> {
+    assert!(
+        checkpoint_interval > 0,
+        "checkpoint_interval must be non-zero"
+    );
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    let mut index = RecordingIndex::default();
+    let mut byte_offset = 0;
+
+    for (i, event) in timestamped_inputs.events.iter().enumerate() {
+        if i % checkpoint_interval == 0 {
+            index.checkpoints.push(IndexCheckpoint {
+                frame: event.frame,
+                time_since_startup: event.time_since_startup,
+                byte_offset,
+            });
+        }
+
+        let line = ron::ser::to_string(event).map_err(std::io::Error::other)?;
+        writeln!(writer, "{line}")?;
+        byte_offset += line.len() as u64 + 1;
+    }
+    writer.flush()?;
+
+    let index = ron::ser::to_string(&index).map_err(std::io::Error::other)?;
+    std::fs::write(index_path(path), index)
+}
+
+/// A recording written by [`write_seekable_recording`], which can load windows of events without reading the whole file.
+///
+/// Only the [`RecordingIndex`] is read when the recording is opened.
+#[derive(Debug, Clone)]
+pub struct SeekableRecording {
+    path: PathBuf,
+    index: RecordingIndex,
+    bytes_read: u64,
+}
+
+impl SeekableRecording {
+    /// Opens the seekable recording at `path`, reading its `.idx` sidecar file.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, TimestampedInputsError> {
+        let path = path.into();
+        let index =
+            std::fs::read_to_string(index_path(&path)).map_err(TimestampedInputsError::Fs)?;
+        let index = ron::de::from_str(&index).map_err(TimestampedInputsError::Ron)?;
+
+        Ok(SeekableRecording {
+            path,
+            index,
+            bytes_read: 0,
+        })
+    }
+
+    /// The [`RecordingIndex`] of this recording.
+    pub fn index(&self) -> &RecordingIndex {
+        &self.index
+    }
+
+    /// The total number of bytes of the recording file read so far.
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+
+    /// Loads all events between the `start` and `end` frames (inclusive).
+    ///
+    /// Reading begins at the last checkpoint before `start`, and stops once an event after `end` is found.
+    pub fn load_frames(
+        &mut self,
+        start: FrameCount,
+        end: FrameCount,
+    ) -> Result<TimestampedInputs, TimestampedInputsError> {
+        // Several events can share a frame, so the checkpoint must come strictly before `start`
+        let byte_offset = self
+            .index
+            .checkpoints
+            .iter()
+            .take_while(|checkpoint| checkpoint.frame < start)
+            .last()
+            .map(|checkpoint| checkpoint.byte_offset)
+            .unwrap_or_default();
+
+        let mut file = File::open(&self.path).map_err(TimestampedInputsError::Fs)?;
+        file.seek(SeekFrom::Start(byte_offset))
+            .map_err(TimestampedInputsError::Fs)?;
+
+        let mut timestamped_inputs = TimestampedInputs::default();
+        let mut reader = BufReader::new(file);
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let bytes = reader
+                .read_line(&mut line)
+                .map_err(TimestampedInputsError::Fs)?;
+            if bytes == 0 {
+                break;
+            }
+            self.bytes_read += bytes as u64;
+
+            let event: TimestampedInputEvent =
+                ron::de::from_str(&line).map_err(TimestampedInputsError::Ron)?;
+            if event.frame > end {
+                break;
+            }
+            if event.frame >= start {
+                timestamped_inputs.events.push(event);
+            }
+        }
+
+        Ok(timestamped_inputs)
+    }
+
+    /// Loads every event in the recording.
+    pub fn load_all(&mut self) -> Result<TimestampedInputs, TimestampedInputsError> {
+        self.load_frames(FrameCount(0), FrameCount(u32::MAX))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::input::keyboard::{Key, KeyCode, KeyboardInput};
+    use bevy::input::ButtonState;
+    use bevy::prelude::Entity;
+
+    #[test]
+    fn lenient_deserialization() {
+        let recording = r#"(
+    events: [
+        (
+            frame: 1,
+            time_since_startup: (secs: 0, nanos: 16000000),
+            input_event: Keyboard((
+                key_code: KeyH,
+                logical_key: Character("h"),
+                state: Pressed,
+                window: 4294967296,
+                repeat: false,
+            )),
+        ),
+        (
+            frame: 2,
+            time_since_startup: (secs: 0, nanos: 32000000),
+            input_event: Touch((
+                id: 3,
+                position: (10.0, 20.0),
+                phase: Started,
+            )),
+        ),
+        (
+            frame: 3,
+            time_since_startup: (secs: 0, nanos: 48000000),
+            input_event: AppExit,
+        ),
+    ],
+    cursor: 0,
+)"#;
+
+        assert!(ron::de::from_str::<TimestampedInputs>(recording).is_err());
+
+        let lenient: LenientTimestampedInputs = ron::de::from_str(recording).unwrap();
+        let (_, dropped) = lenient.into_timestamped_inputs();
+        assert_eq!(dropped, 1);
+
+        let path = std::env::temp_dir().join("leafwing_input_playback_lenient_deserialization.ron");
+        std::fs::write(&path, recording).unwrap();
+        let playback_path = PlaybackFilePath::new(path.to_str().unwrap());
+        assert!(
+            crate::input_playback::deserialize_timestamped_inputs(&playback_path)
+                .unwrap()
+                .is_err()
+        );
+        let timestamped_inputs =
+            crate::input_playback::deserialize_timestamped_inputs_lenient(&playback_path)
+                .unwrap()
+                .unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(timestamped_inputs.len(), 2);
+        assert!(matches!(
+            timestamped_inputs.events[0].input_event,
+            InputEvent::Keyboard(KeyboardInput {
+                key_code: KeyCode::KeyH,
+                ..
+            })
+        ));
+        assert_eq!(timestamped_inputs.events[0].frame, FrameCount(1));
+        assert_eq!(
+            timestamped_inputs.events[1].input_event,
+            InputEvent::AppExit
+        );
+        assert_eq!(timestamped_inputs.events[1].frame, FrameCount(3));
+    }
+
+    #[test]
+    fn recording_format_header() {
+        use crate::timestamped_input::RECORDING_FORMAT_VERSION;
+
+        // Recordings are saved with the current format version, and checked when they are read
+        let mut timestamped_inputs = TimestampedInputs {
+            header: RecordingHeader::unversioned(),
+            ..Default::default()
+        };
+        timestamped_inputs.send(FrameCount(1), Duration::from_secs(1), InputEvent::AppExit);
+        let saved = ron::ser::to_string(&timestamped_inputs).unwrap();
+        let loaded = read_recording(saved.as_bytes()).unwrap();
+        assert_eq!(loaded.header.version, RECORDING_FORMAT_VERSION);
+        assert_eq!(loaded.events, timestamped_inputs.events);
+
+        // Recordings saved before the format was versioned have no header
+        let unversioned = "(events: [], cursor: 0)";
+        let loaded = read_recording(unversioned.as_bytes()).unwrap();
+        assert_eq!(loaded.header, RecordingHeader::unversioned());
+
+        // Newer recordings are rejected, even if their events cannot be parsed
+        let newer_version = RECORDING_FORMAT_VERSION + 1;
+        let newer = format!(
+            "(header: (version: {newer_version}), events: [(frame: 1, time_since_startup: (secs: 0, nanos: 0), input_event: Touch((id: 3)))], cursor: 0)"
+        );
+        assert!(matches!(
+            read_recording(newer.as_bytes()),
+            Err(TimestampedInputsError::IncompatibleVersion(version)) if version == newer_version
+        ));
+    }
+
+    #[test]
+    fn seek_with_index() {
+        let mut timestamped_inputs = TimestampedInputs::default();
+        for i in 0..1000 {
+            let event = KeyboardInput {
+                logical_key: Key::Space,
+                key_code: KeyCode::Space,
+                state: if i % 2 == 0 {
+                    ButtonState::Pressed
+                } else {
+                    ButtonState::Released
+                },
+                window: Entity::PLACEHOLDER,
+                repeat: false,
+            };
+            timestamped_inputs.send(
+                FrameCount(i / 2),
+                Duration::from_millis(i as u64 * 8),
+                event.into(),
+            );
+        }
+
+        let path = std::env::temp_dir().join("leafwing_input_playback_seek_with_index.ron");
+        write_seekable_recording(&timestamped_inputs, &path, 16).unwrap();
+
+        let mut full = SeekableRecording::open(&path).unwrap();
+        let all_events = full.load_all().unwrap();
+        assert_eq!(all_events, timestamped_inputs);
+
+        let mut seekable = SeekableRecording::open(&path).unwrap();
+        let window = seekable
+            .load_frames(FrameCount(300), FrameCount(309))
+            .unwrap();
+        assert_eq!(window.len(), 20);
+        assert_eq!(window.events[..], timestamped_inputs.events[600..620]);
+        assert!(seekable.bytes_read() * 10 < full.bytes_read());
+
+        std::fs::remove_file(index_path(&path)).unwrap();
+        std::fs::remove_file(path).unwrap();
+    }
+}
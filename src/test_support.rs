@@ -0,0 +1,264 @@
+//! Utilities for deterministically testing input playback, available with the `test-support` feature.
+//!
+//! Driving [`Time`] by hand is fiddly, so the [`DeterministicPlaybackHarness`] advances it in fixed steps
+//! and reports the input events that were played back on each step.
+//! For simple assertions, [`collect_playback`] returns every event played back over a number of frames.
+//! Mock gamepads can be connected in headless apps with [`register_gamepad`].
+
+use bevy::app::{App, AppExit};
+use bevy::ecs::{
+    prelude::*,
+    system::{SystemParam, SystemState},
+};
+use bevy::input::{
+    gamepad::{GamepadConnection, GamepadConnectionEvent, GamepadEvent, GamepadRumbleRequest},
+    keyboard::KeyboardInput,
+    mouse::{AccumulatedMouseMotion, AccumulatedMouseScroll, MouseButtonInput, MouseWheel},
+    InputPlugin,
+};
+use bevy::math::Vec2;
+#[cfg(feature = "picking")]
+use bevy::picking::events::{Click, Down, Pointer, Up};
+use bevy::time::{Time, TimeUpdateStrategy, Virtual};
+use bevy::utils::Duration;
+use bevy::window::{CursorMoved, Ime, WindowMoved, WindowPlugin};
+use bevy::MinimalPlugins;
+
+use crate::input_playback::{
+    BeginInputPlayback, InputPlaybackPlugin, InputPlaybackSource, PlaybackStrategy,
+};
+#[cfg(feature = "picking")]
+use crate::picking::{PointerEvent, PointerEventKind};
+use crate::timestamped_input::{InputEvent, TimestampedInputs};
+
+/// Connects a mock gamepad on the existing `gamepad` entity, as if it had just been plugged in.
+///
+/// This sends a [`GamepadConnectionEvent`], which Bevy uses to add the [`Gamepad`](bevy::input::gamepad::Gamepad) component on the next update,
+/// along with the matching [`GamepadEvent::Connection`], so that the connection is captured and played back like a real one.
+pub fn register_gamepad(app: &mut App, gamepad: Entity) {
+    let connection_event = GamepadConnectionEvent::new(
+        gamepad,
+        GamepadConnection::Connected {
+            name: "Mock Gamepad".to_string(),
+            vendor_id: None,
+            product_id: None,
+        },
+    );
+    app.world_mut().send_event(connection_event.clone());
+    app.world_mut()
+        .send_event(GamepadEvent::Connection(connection_event));
+}
+
+/// Plays back the `inputs` under [`PlaybackStrategy::FrameCount`] for `frames` updates of the `app`, returning every input event that was sent.
+///
+/// The `app` must already contain the [`InputPlaybackPlugin`].
+/// Events are reported in the same way as by [`DeterministicPlaybackHarness::step`], one update after another.
+pub fn collect_playback(
+    app: &mut App,
+    inputs: TimestampedInputs,
+    frames: usize,
+) -> Vec<InputEvent> {
+    #[cfg(feature = "picking")]
+    register_pointer_events(app);
+    let mut event_readers = SystemState::<PlaybackEventReaders>::new(app.world_mut());
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::FrameCount,
+        source: Some(InputPlaybackSource::from_inputs(inputs)),
+        ..Default::default()
+    });
+    app.world_mut().flush();
+
+    let mut input_events = Vec::new();
+    for _ in 0..frames {
+        app.update();
+        input_events.extend(event_readers.get_mut(app.world_mut()).read_all());
+    }
+    input_events
+}
+
+/// Pointer events are only registered by the picking plugins, which headless apps rarely add
+#[cfg(feature = "picking")]
+fn register_pointer_events(app: &mut App) {
+    app.add_event::<Pointer<Down>>()
+        .add_event::<Pointer<Up>>()
+        .add_event::<Pointer<Click>>();
+}
+
+/// Runs input playback in an [`App`] whose [`Time`] advances by a fixed `step` on every update.
+///
+/// The input events sent during each update are collected, grouped by type in the order of [`InputEvent`]'s variants.
+/// These are read from the [`Events`] resources directly, so inputs sent by other sources are reported as well.
+/// The [`AccumulatedMouseMotion`] and [`AccumulatedMouseScroll`] resources are reported whenever they are non-zero,
+/// while [`InputEvent::IdleMarker`]s are never played back, so they are never reported.
+pub struct DeterministicPlaybackHarness {
+    app: App,
+    step: Duration,
+    event_readers: SystemState<PlaybackEventReaders<'static, 'static>>,
+}
+
+/// The [`EventReader`] types that correspond to the input event types stored in [`InputEvent`]
+#[derive(SystemParam)]
+struct PlaybackEventReaders<'w, 's> {
+    keyboard_input: EventReader<'w, 's, KeyboardInput>,
+    mouse_button_input: EventReader<'w, 's, MouseButtonInput>,
+    mouse_wheel: EventReader<'w, 's, MouseWheel>,
+    cursor_moved: EventReader<'w, 's, CursorMoved>,
+    gamepad: EventReader<'w, 's, GamepadEvent>,
+    app_exit: EventReader<'w, 's, AppExit>,
+    accumulated_mouse_motion: Option<Res<'w, AccumulatedMouseMotion>>,
+    accumulated_mouse_scroll: Option<Res<'w, AccumulatedMouseScroll>>,
+    gamepad_rumble: EventReader<'w, 's, GamepadRumbleRequest>,
+    #[cfg(feature = "picking")]
+    pointer_down: EventReader<'w, 's, Pointer<Down>>,
+    #[cfg(feature = "picking")]
+    pointer_up: EventReader<'w, 's, Pointer<Up>>,
+    #[cfg(feature = "picking")]
+    pointer_click: EventReader<'w, 's, Pointer<Click>>,
+    window_moved: EventReader<'w, 's, WindowMoved>,
+    ime: EventReader<'w, 's, Ime>,
+}
+
+impl PlaybackEventReaders<'_, '_> {
+    /// Reads all input events sent since the last read, grouped by type in the order of [`InputEvent`]'s variants.
+    fn read_all(&mut self) -> Vec<InputEvent> {
+        let mut input_events = Vec::new();
+        input_events.extend(self.keyboard_input.read().cloned().map(InputEvent::from));
+        input_events.extend(
+            self.mouse_button_input
+                .read()
+                .cloned()
+                .map(InputEvent::from),
+        );
+        input_events.extend(self.mouse_wheel.read().cloned().map(InputEvent::from));
+        input_events.extend(self.cursor_moved.read().cloned().map(InputEvent::from));
+        input_events.extend(self.gamepad.read().cloned().map(InputEvent::from));
+        input_events.extend(self.app_exit.read().cloned().map(InputEvent::from));
+        input_events.extend(
+            self.accumulated_mouse_motion
+                .as_deref()
+                .filter(|motion| motion.delta != Vec2::ZERO)
+                .map(|motion| InputEvent::from(*motion)),
+        );
+        input_events.extend(
+            self.accumulated_mouse_scroll
+                .as_deref()
+                .filter(|scroll| scroll.delta != Vec2::ZERO)
+                .map(|scroll| InputEvent::from(*scroll)),
+        );
+        input_events.extend(self.gamepad_rumble.read().cloned().map(InputEvent::from));
+        #[cfg(feature = "picking")]
+        {
+            let down = self
+                .pointer_down
+                .read()
+                .filter_map(|e| PointerEvent::new(PointerEventKind::Down, e, e.button, &e.hit));
+            let up = self
+                .pointer_up
+                .read()
+                .filter_map(|e| PointerEvent::new(PointerEventKind::Up, e, e.button, &e.hit));
+            let click = self.pointer_click.read().filter_map(|e| {
+                let kind = PointerEventKind::Click {
+                    duration: e.duration,
+                };
+                PointerEvent::new(kind, e, e.button, &e.hit)
+            });
+            input_events.extend(down.chain(up).chain(click).map(InputEvent::from));
+        }
+        input_events.extend(self.window_moved.read().cloned().map(InputEvent::from));
+        input_events.extend(self.ime.read().cloned().map(InputEvent::from));
+        input_events
+    }
+}
+
+impl DeterministicPlaybackHarness {
+    /// Creates a harness around a minimal headless [`App`] with the [`InputPlaybackPlugin`] added.
+    pub fn new(step: Duration) -> Self {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            WindowPlugin::default(),
+            InputPlugin,
+            InputPlaybackPlugin,
+        ));
+        Self::from_app(app, step)
+    }
+
+    /// Creates a harness around the provided `app`, which must already contain the [`InputPlaybackPlugin`].
+    ///
+    /// [`Time`] is advanced by exactly `step` on each update, even if this exceeds [`Time::<Virtual>::max_delta`].
+    pub fn from_app(mut app: App, step: Duration) -> Self {
+        #[cfg(feature = "picking")]
+        register_pointer_events(&mut app);
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(step));
+        let mut virtual_time = app.world_mut().resource_mut::<Time<Virtual>>();
+        let max_delta = virtual_time.max_delta().max(step);
+        virtual_time.set_max_delta(max_delta);
+
+        let event_readers = SystemState::new(app.world_mut());
+        DeterministicPlaybackHarness {
+            app,
+            step,
+            event_readers,
+        }
+    }
+
+    /// The duration by which [`Time`] advances on each update.
+    pub fn step_duration(&self) -> Duration {
+        self.step
+    }
+
+    /// The [`App`] driven by this harness.
+    pub fn app(&mut self) -> &mut App {
+        &mut self.app
+    }
+
+    /// Updates the app once, returning the input events that were sent during that update.
+    pub fn step(&mut self) -> Vec<InputEvent> {
+        self.app.update();
+
+        self.event_readers.get_mut(self.app.world_mut()).read_all()
+    }
+
+    /// Has playback run to completion?
+    ///
+    /// This is the case once playback is [`PlaybackStrategy::Paused`] (as the `...Once` strategies are when they finish),
+    /// or once every event has been played back under an open-ended strategy.
+    /// Looping strategies never finish.
+    pub fn is_finished(&self) -> bool {
+        let world = self.app.world();
+        let (Some(strategy), Some(timestamped_input)) = (
+            world.get_resource::<PlaybackStrategy>(),
+            world.get_resource::<TimestampedInputs>(),
+        ) else {
+            return true;
+        };
+
+        match strategy {
+            PlaybackStrategy::Paused => true,
+            PlaybackStrategy::Time
+            | PlaybackStrategy::FrameCount
+            | PlaybackStrategy::RecordedFrameRate
+            | PlaybackStrategy::Gated
+            | PlaybackStrategy::Immediate => timestamped_input.cursor >= timestamped_input.len(),
+            _ => false,
+        }
+    }
+
+    /// Begins playback, then steps the app until playback [is finished](Self::is_finished) or `max_steps` have run.
+    ///
+    /// Returns the input events sent during each step.
+    pub fn run(
+        &mut self,
+        begin_input_playback: BeginInputPlayback,
+        max_steps: usize,
+    ) -> Vec<Vec<InputEvent>> {
+        self.app.world_mut().trigger(begin_input_playback);
+        self.app.world_mut().flush();
+
+        let mut steps = Vec::new();
+        while steps.len() < max_steps && !self.is_finished() {
+            steps.push(self.step());
+        }
+        steps
+    }
+}
@@ -2,15 +2,30 @@
 //! These are first unified into a [`InputEvent`] enum, then timestamped to create a [`TimestampedInputEvent`].
 //! Those timestamped events are finally stored inside of a [`TimestampedInputs`] resource, which should be used for input capture and playback.
 
+#[cfg(feature = "file")]
+use crate::input_playback::TimestampedInputsError;
 use bevy::app::AppExit;
 use bevy::core::FrameCount;
 use bevy::ecs::prelude::*;
-use bevy::input::gamepad::GamepadEvent;
-use bevy::input::keyboard::KeyboardInput;
-use bevy::input::mouse::{MouseButtonInput, MouseWheel};
-use bevy::utils::Duration;
-use bevy::window::CursorMoved;
-use serde::{Deserialize, Serialize};
+use bevy::input::gamepad::{
+    GamepadButton, GamepadButtonChangedEvent, GamepadEvent, GamepadRumbleIntensity,
+    GamepadRumbleRequest,
+};
+use bevy::input::keyboard::{KeyCode, KeyboardInput};
+use bevy::input::mouse::{
+    AccumulatedMouseMotion, AccumulatedMouseScroll, MouseButton, MouseButtonInput, MouseWheel,
+};
+use bevy::input::{ButtonInput, ButtonState};
+use bevy::math::Vec2;
+use bevy::utils::{Duration, HashMap, HashSet};
+use bevy::window::{CursorMoved, Ime, WindowMoved};
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::{BTreeMap, BTreeSet};
+#[cfg(feature = "file")]
+use std::fs::File;
+use std::ops::Range;
+#[cfg(feature = "file")]
+use std::path::Path;
 
 /// A timestamped device-agnostic user-input event
 ///
@@ -23,6 +38,151 @@ pub struct TimestampedInputEvent {
     pub time_since_startup: Duration,
     /// The [`InputEvent`] that was captured
     pub input_event: InputEvent,
+    /// The time at which the capture system recorded this event, measured from when capture began
+    ///
+    /// Unlike `time_since_startup`, this differs between capture systems that run in the same frame,
+    /// but events captured in the same batch share it.
+    /// This is only recorded when [`PreciseTimestamps`](crate::input_capture::PreciseTimestamps) are enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precise_time: Option<Duration>,
+    /// The modifier keys that were held when this event was captured
+    ///
+    /// This is only recorded for keyboard and mouse events when [`CaptureModifiers`](crate::input_capture::CaptureModifiers) is enabled,
+    /// and is metadata for analysis: it is not played back.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modifiers: Option<Modifiers>,
+}
+
+/// A snapshot of which modifier keys are held, on either side of the keyboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct Modifiers {
+    /// Is either Control key held?
+    pub ctrl: bool,
+    /// Is either Alt key held?
+    pub alt: bool,
+    /// Is either Shift key held?
+    pub shift: bool,
+    /// Is either Super key held? This is the Windows key, or the Command key on macOS.
+    pub super_key: bool,
+}
+
+impl Modifiers {
+    /// Reads the modifier keys held according to the provided `keys`.
+    pub fn from_keys(keys: &ButtonInput<KeyCode>) -> Self {
+        Modifiers {
+            ctrl: keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]),
+            alt: keys.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]),
+            shift: keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]),
+            super_key: keys.any_pressed([KeyCode::SuperLeft, KeyCode::SuperRight]),
+        }
+    }
+}
+
+/// The version of the format in which [`TimestampedInputs`] are recorded.
+///
+/// This is incremented whenever a change to the format means that older versions of this crate can no longer read new recordings.
+/// Recordings made before the format was versioned are version `0`.
+pub const RECORDING_FORMAT_VERSION: u32 = 1;
+
+/// Can recordings made with the provided format `version` be read by this version of the crate?
+///
+/// Recordings are backwards compatible: every version up to and including [`RECORDING_FORMAT_VERSION`] can be read.
+/// Recordings from newer versions may contain data that this version does not understand, and so are not compatible.
+pub fn is_compatible(version: u32) -> bool {
+    version <= RECORDING_FORMAT_VERSION
+}
+
+/// Would replaying the recordings `a` and `b` hold down the same buttonlike inputs on the same frames?
+///
+/// The keyboard, mouse button and gamepad buttons that are held at the end of each frame are compared,
+/// so events that are reordered within a frame (or moved between frames without changing the held state) do not matter.
+/// All other kinds of events are ignored.
+pub fn equivalent_trajectories(a: &TimestampedInputs, b: &TimestampedInputs) -> bool {
+    a.held_state_changes() == b.held_state_changes()
+}
+
+/// Information about a recording as a whole, which is saved ahead of its events.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordingHeader {
+    /// The [`RECORDING_FORMAT_VERSION`] that the recording was saved with.
+    ///
+    /// Recordings are always saved in the current format, so this is serialized as the current [`RECORDING_FORMAT_VERSION`],
+    /// even if the recording was loaded from an older version.
+    #[serde(serialize_with = "serialize_current_format_version")]
+    pub version: u32,
+    /// Arbitrary key-value pairs describing the recording, such as the build or platform it was captured on.
+    ///
+    /// These are copied from the [`RecordingMetadata`](crate::input_capture::RecordingMetadata) resource during capture,
+    /// and have no effect on playback.
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_sorted_metadata"
+    )]
+    pub metadata: HashMap<String, String>,
+    /// The logical size of the captured window when input capture began, if it could be found.
+    ///
+    /// This is recorded by [`BeginInputCapture`](crate::input_capture::BeginInputCapture),
+    /// and can be compared against the size of the playback window to rescale cursor positions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recorded_resolution: Option<Vec2>,
+}
+
+impl RecordingHeader {
+    /// The header of recordings saved before the format was versioned, which do not contain a header.
+    pub fn unversioned() -> Self {
+        RecordingHeader {
+            version: 0,
+            metadata: HashMap::default(),
+            recorded_resolution: None,
+        }
+    }
+}
+
+impl Default for RecordingHeader {
+    fn default() -> Self {
+        RecordingHeader {
+            version: RECORDING_FORMAT_VERSION,
+            metadata: HashMap::default(),
+            recorded_resolution: None,
+        }
+    }
+}
+
+fn serialize_current_format_version<S: Serializer>(
+    _version: &u32,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u32(RECORDING_FORMAT_VERSION)
+}
+
+/// Metadata is serialized in order of its keys, so that saving the same recording always produces the same file.
+fn serialize_sorted_metadata<S: Serializer>(
+    metadata: &HashMap<String, String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    metadata
+        .iter()
+        .collect::<BTreeMap<_, _>>()
+        .serialize(serializer)
+}
+
+/// A user-provided simulation tick, for apps whose simulation advances independently of rendered frames.
+///
+/// Fixed-timestep games should increment this whenever their simulation advances,
+/// and can then capture and play back inputs against it rather than [`FrameCount`],
+/// see [`BeginInputCapture::use_sim_tick`](crate::input_capture::BeginInputCapture::use_sim_tick)
+/// and [`BeginInputPlayback::use_sim_tick`](crate::input_playback::BeginInputPlayback::use_sim_tick).
+///
+/// Ticks are stored in the `frame` field of each [`TimestampedInputEvent`], and so wrap at [`u32::MAX`].
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SimTick(pub u64);
+
+impl SimTick {
+    /// The [`FrameCount`] under which events captured on this tick are stored.
+    pub fn frame(&self) -> FrameCount {
+        FrameCount(self.0 as u32)
+    }
 }
 
 /// A resource that stores the complete event-like list of [`TimestampedInputs`]
@@ -30,6 +190,11 @@ pub struct TimestampedInputEvent {
 /// Read and write to this struct when performing input capture and playback
 #[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct TimestampedInputs {
+    /// Information about the recording as a whole, such as its format version.
+    ///
+    /// Recordings saved without a header are loaded with [`RecordingHeader::unversioned`].
+    #[serde(default = "RecordingHeader::unversioned")]
+    pub header: RecordingHeader,
     /// The underlying [`TimestampedInputEvent`] data
     ///
     /// New events are pushed to the back of the list.
@@ -47,6 +212,30 @@ pub struct TimestampedInputs {
 }
 
 impl TimestampedInputs {
+    /// Creates a new, empty [`TimestampedInputs`] with space for at least `capacity` events.
+    ///
+    /// This avoids reallocations when building large streams of events by hand.
+    pub fn with_capacity(capacity: usize) -> Self {
+        TimestampedInputs {
+            header: RecordingHeader::default(),
+            events: Vec::with_capacity(capacity),
+            cursor: 0,
+        }
+    }
+
+    /// Creates a new [`TimestampedInputs`] from an iterable of events, each paired with the frame and time that it was sent.
+    ///
+    /// Events are stored in the order provided.
+    pub fn from_timed<E: Into<InputEvent>>(
+        timed_events: impl IntoIterator<Item = (FrameCount, Duration, E)>,
+    ) -> Self {
+        let mut timestamped_inputs = TimestampedInputs::default();
+        for (frame, time_since_startup, event) in timed_events {
+            timestamped_inputs.send(frame, time_since_startup, event.into());
+        }
+        timestamped_inputs
+    }
+
     /// Records an `input_event`, making note of the frame and time that it was sent.
     pub fn send(
         &mut self,
@@ -58,6 +247,8 @@ impl TimestampedInputs {
             frame,
             time_since_startup,
             input_event,
+            precise_time: None,
+            modifiers: None,
         });
     }
 
@@ -79,6 +270,291 @@ impl TimestampedInputs {
         self.cursor = 0;
     }
 
+    /// Advances the `cursor` to the first unread event that satisfies the `predicate`, returning its index.
+    ///
+    /// The matching event will be the next event read.
+    /// If no unread event matches, the `cursor` is not moved and [`None`] is returned.
+    pub fn seek_to_first_matching(
+        &mut self,
+        predicate: impl FnMut(&TimestampedInputEvent) -> bool,
+    ) -> Option<usize> {
+        let offset = self.events.get(self.cursor..)?.iter().position(predicate)?;
+        self.cursor += offset;
+        Some(self.cursor)
+    }
+
+    /// Replaces all events within the `range` of frames with the events of the `replacement` recording.
+    ///
+    /// The `replacement` is rebased so that its first event occurs at the start of the `range`,
+    /// at the time of the first replaced event (or of the last event before the `range`, if nothing was replaced).
+    /// Replacement events that fall past the end of the `range` once rebased are dropped.
+    ///
+    /// The events are then sorted by [`FrameCount`], and the cursor is reset.
+    pub fn splice_range(&mut self, range: Range<FrameCount>, replacement: TimestampedInputs) {
+        let start_time = self
+            .events
+            .iter()
+            .find(|event| range.contains(&event.frame))
+            .or_else(|| {
+                self.events
+                    .iter()
+                    .rev()
+                    .find(|event| event.frame < range.start)
+            })
+            .map(|event| event.time_since_startup)
+            .unwrap_or_default();
+        self.events.retain(|event| !range.contains(&event.frame));
+
+        if let Some(first) = replacement.events.first() {
+            let (first_frame, first_time) = (first.frame, first.time_since_startup);
+            let rebased = replacement.events.into_iter().filter_map(|event| {
+                let frame = FrameCount(range.start.0 + event.frame.0.saturating_sub(first_frame.0));
+                range.contains(&frame).then(|| TimestampedInputEvent {
+                    frame,
+                    time_since_startup: start_time
+                        + event.time_since_startup.saturating_sub(first_time),
+                    input_event: event.input_event,
+                    // Precise timestamps are measured from the start of a different capture
+                    precise_time: None,
+                    modifiers: event.modifiers,
+                })
+            });
+            self.events.extend(rebased);
+        }
+
+        self.sort(SortingStrategy::FrameCount);
+        self.reset_cursor();
+    }
+
+    /// Records the difference between the `base` recording and a `modified` version of it, see [`RecordingPatch`].
+    ///
+    /// The original can be recovered from the `base` and the patch with [`TimestampedInputs::apply_patch`].
+    pub fn diff_patch(base: &Self, modified: &Self) -> RecordingPatch {
+        let prefix_len = base
+            .events
+            .iter()
+            .zip(&modified.events)
+            .take_while(|(base_event, modified_event)| base_event == modified_event)
+            .count();
+        // The suffix may not overlap the prefix in either recording
+        let max_suffix_len = base.events.len().min(modified.events.len()) - prefix_len;
+        let suffix_len = base
+            .events
+            .iter()
+            .rev()
+            .zip(modified.events.iter().rev())
+            .take(max_suffix_len)
+            .take_while(|(base_event, modified_event)| base_event == modified_event)
+            .count();
+
+        RecordingPatch {
+            header: modified.header.clone(),
+            base_len: base.events.len(),
+            prefix_len,
+            suffix_len,
+            events: modified.events[prefix_len..modified.events.len() - suffix_len].to_vec(),
+        }
+    }
+
+    /// Applies a `patch` created by [`TimestampedInputs::diff_patch`] to the `base` recording that it was created against.
+    ///
+    /// The cursor of the returned recording is at its start.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `base` does not have as many events as the recording the `patch` was created against.
+    pub fn apply_patch(base: &Self, patch: &RecordingPatch) -> Self {
+        assert_eq!(
+            base.events.len(),
+            patch.base_len,
+            "The patch was not created against this recording"
+        );
+
+        let suffix_start = base.events.len() - patch.suffix_len;
+        TimestampedInputs {
+            header: patch.header.clone(),
+            events: base.events[..patch.prefix_len]
+                .iter()
+                .chain(&patch.events)
+                .chain(&base.events[suffix_start..])
+                .cloned()
+                .collect(),
+            cursor: 0,
+        }
+    }
+
+    /// Puts this recording into a canonical form, so that equivalent recordings are serialized identically.
+    ///
+    /// Events are stably sorted by their [`FrameCount`], then their time since startup, then their [`InputEventKind`],
+    /// and consecutive events that are exact duplicates of each other are removed. The cursor is reset.
+    pub fn canonicalize(&mut self) {
+        self.events.sort_by_key(|event| {
+            (
+                event.frame,
+                event.time_since_startup,
+                InputEventKind::from(&event.input_event),
+            )
+        });
+        self.events.dedup();
+        self.reset_cursor();
+    }
+
+    /// Reassigns the [`FrameCount`] of each event, so that the distinct frames of the recording are numbered `0, 1, 2, ...`.
+    ///
+    /// This removes the gaps between frames left behind by editing, such as with [`TimestampedInputs::splice_range`].
+    /// Events that shared a frame still share a frame, the order of the frames is preserved, and timestamps are left unchanged.
+    pub fn renumber_frames(&mut self) {
+        let frames: BTreeMap<FrameCount, FrameCount> = self
+            .events
+            .iter()
+            .map(|event| event.frame)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .enumerate()
+            .map(|(index, frame)| (frame, FrameCount(index as u32)))
+            .collect();
+
+        for event in self.events.iter_mut() {
+            event.frame = frames[&event.frame];
+        }
+    }
+
+    /// Appends the events of the `other` recording to the end of this one, so that they are played back afterwards.
+    ///
+    /// The events of `other` are shifted to begin one frame after the last event of this recording,
+    /// with the gap in time between them matching the [`TimestampedInputs::recorded_frame_duration`] of this recording.
+    /// The relative timing of the events within `other` is preserved.
+    pub fn append_after(&mut self, other: TimestampedInputs) {
+        let Some(last) = self.events.last() else {
+            self.events = other.events;
+            return;
+        };
+        let Some(first) = other.events.first() else {
+            return;
+        };
+
+        let frame_offset = last.frame.0.wrapping_add(1).wrapping_sub(first.frame.0);
+        let start_time =
+            last.time_since_startup + self.recorded_frame_duration().unwrap_or_default();
+        let first_time = first.time_since_startup;
+        self.events
+            .extend(other.events.into_iter().map(|event| TimestampedInputEvent {
+                frame: FrameCount(event.frame.0.wrapping_add(frame_offset)),
+                time_since_startup: start_time
+                    + event.time_since_startup.saturating_sub(first_time),
+                input_event: event.input_event,
+                // Precise timestamps are measured from the start of a different capture
+                precise_time: None,
+                modifiers: event.modifiers,
+            }));
+    }
+
+    /// Loads each of the recordings at the provided `paths`, and concatenates them in order using [`TimestampedInputs::append_after`].
+    ///
+    /// This produces a single continuous recording, which is handy for playing several recordings end-to-end.
+    /// If any of the files cannot be loaded, the returned [`TimestampedInputsError::File`] contains its path.
+    #[cfg(feature = "file")]
+    pub fn from_files(paths: &[impl AsRef<Path>]) -> Result<Self, TimestampedInputsError> {
+        let mut playlist = TimestampedInputs::default();
+        for path in paths {
+            let path = path.as_ref();
+            let recording = File::open(path)
+                .map_err(TimestampedInputsError::Fs)
+                .and_then(crate::serde::read_recording)
+                .map_err(|error| {
+                    TimestampedInputsError::File(path.to_path_buf(), Box::new(error))
+                })?;
+            playlist.append_after(recording);
+        }
+        Ok(playlist)
+    }
+
+    /// Merges the events of the `other` recording into this one, keeping all events from both.
+    ///
+    /// This is equivalent to [`TimestampedInputs::merge_with`] with [`MergePolicy::KeepBoth`].
+    pub fn merge(&mut self, other: TimestampedInputs) {
+        self.merge_with(other, MergePolicy::KeepBoth);
+    }
+
+    /// Merges the events of the `other` recording into this one, using the `policy` to resolve conflicts.
+    ///
+    /// Two events conflict when they occur on the same [`FrameCount`] and have the same [`InputEventKind`].
+    /// The events are then sorted by [`FrameCount`], with events from `self` first within each frame, and the cursor is reset.
+    pub fn merge_with(&mut self, other: TimestampedInputs, policy: MergePolicy) {
+        let coordinates = |events: &[TimestampedInputEvent]| -> HashSet<(u32, InputEventKind)> {
+            events
+                .iter()
+                .map(|event| (event.frame.0, InputEventKind::from(&event.input_event)))
+                .collect()
+        };
+        let is_conflict = |event: &TimestampedInputEvent,
+                           coordinates: &HashSet<(u32, InputEventKind)>| {
+            coordinates.contains(&(event.frame.0, InputEventKind::from(&event.input_event)))
+        };
+
+        match policy {
+            MergePolicy::KeepBoth => self.events.extend(other.events),
+            MergePolicy::PreferSelf => {
+                let own_coordinates = coordinates(&self.events);
+                self.events.extend(
+                    other
+                        .events
+                        .into_iter()
+                        .filter(|event| !is_conflict(event, &own_coordinates)),
+                );
+            }
+            MergePolicy::PreferOther => {
+                let other_coordinates = coordinates(&other.events);
+                self.events
+                    .retain(|event| !is_conflict(event, &other_coordinates));
+                self.events.extend(other.events);
+            }
+        }
+
+        self.sort(SortingStrategy::FrameCount);
+        self.reset_cursor();
+    }
+
+    /// Returns all stored [`InputEvent::Gamepad`] events for the gamepad with the provided `entity`, in order.
+    pub fn gamepad_events(&self, entity: Entity) -> Vec<&TimestampedInputEvent> {
+        self.events
+            .iter()
+            .filter(|event| event.input_event.gamepad() == Some(entity))
+            .collect()
+    }
+
+    /// Groups all stored events by the frame they were recorded on.
+    ///
+    /// Within each frame, events are kept in the order they were recorded.
+    /// This is the natural shape for frame-indexed input buffers, such as those used in lockstep or rollback netcode.
+    pub fn per_frame_snapshots(&self) -> BTreeMap<FrameCount, Vec<InputEvent>> {
+        let mut snapshots: BTreeMap<FrameCount, Vec<InputEvent>> = BTreeMap::new();
+        for event in &self.events {
+            snapshots
+                .entry(event.frame)
+                .or_default()
+                .push(event.input_event.clone());
+        }
+        snapshots
+    }
+
+    /// Returns an iterator over all stored events, with a [`PlaybackStep::Wait`] before each event that was recorded later than the one before it.
+    ///
+    /// The wait before the first event is measured from startup, and waits of zero length are skipped.
+    /// This is useful for custom playback runners that sleep between events.
+    /// This method should only be used on [`TimestampedInputs`] resources that are sorted by [`SortingStrategy::TimeSinceStartup`].
+    pub fn iter_with_waits(&self) -> impl Iterator<Item = PlaybackStep> + '_ {
+        self.events
+            .iter()
+            .scan(Duration::ZERO, |last_time, event| {
+                let gap = event.time_since_startup.saturating_sub(*last_time);
+                *last_time = event.time_since_startup;
+                let wait = (!gap.is_zero()).then_some(PlaybackStep::Wait(gap));
+                Some(wait.into_iter().chain([PlaybackStep::Event(event.clone())]))
+            })
+            .flatten()
+    }
+
     /// Get the earliest and latest [`FrameCount`] of all events stored
     pub fn frame_range(&self) -> Option<(FrameCount, FrameCount)> {
         debug_assert!(self.is_sorted(SortingStrategy::FrameCount));
@@ -105,6 +581,367 @@ impl TimestampedInputs {
         }
     }
 
+    /// Do all of the events in this recording occur on the same frame and at the same time?
+    ///
+    /// This is only `true` for recordings with more than one event,
+    /// and usually indicates a malformed or partially-written recording.
+    pub fn has_zero_span(&self) -> bool {
+        if self.len() < 2 {
+            return false;
+        }
+
+        let first = &self.events[0];
+        self.events.iter().all(|event| {
+            event.frame == first.frame && event.time_since_startup == first.time_since_startup
+        })
+    }
+
+    /// Get the average duration of a single frame, as it was recorded.
+    ///
+    /// Returns `None` if all events stored occurred within the same frame.
+    pub fn recorded_frame_duration(&self) -> Option<Duration> {
+        let (start_frame, end_frame) = self.frame_range()?;
+        let (start_time, end_time) = self.time_range()?;
+        let frames = end_frame.0.wrapping_sub(start_frame.0);
+
+        (frames > 0).then(|| (end_time - start_time) / frames)
+    }
+
+    /// How long each key was held for, with each key's holds listed in the order they were recorded.
+    ///
+    /// Each press is paired with the next release of the same [`KeyCode`], measured by time since startup.
+    /// Repeated presses while a key is held do not restart its hold, and presses that are never released are ignored.
+    pub fn key_hold_durations(&self) -> HashMap<KeyCode, Vec<Duration>> {
+        let mut pressed_at: HashMap<KeyCode, Duration> = HashMap::default();
+        let mut hold_durations: HashMap<KeyCode, Vec<Duration>> = HashMap::default();
+
+        for event in &self.events {
+            let InputEvent::Keyboard(keyboard_input) = &event.input_event else {
+                continue;
+            };
+            match keyboard_input.state {
+                ButtonState::Pressed => {
+                    pressed_at
+                        .entry(keyboard_input.key_code)
+                        .or_insert(event.time_since_startup);
+                }
+                ButtonState::Released => {
+                    if let Some(pressed) = pressed_at.remove(&keyboard_input.key_code) {
+                        hold_durations
+                            .entry(keyboard_input.key_code)
+                            .or_default()
+                            .push(event.time_since_startup.saturating_sub(pressed));
+                    }
+                }
+            }
+        }
+        hold_durations
+    }
+
+    /// The [`InputDevice`] that produced the most events in this recording.
+    ///
+    /// Ties are broken in favor of the device that is declared first in [`InputDevice`].
+    /// If the recording contains no events from any device, [`InputDevice::Unknown`] is returned.
+    pub fn primary_device(&self) -> InputDevice {
+        let mut counts: HashMap<InputDevice, usize> = HashMap::default();
+        for event in self.events.iter() {
+            if let Some(device) = InputEventKind::from(&event.input_event).device() {
+                *counts.entry(device).or_default() += 1;
+            }
+        }
+
+        let mut primary_device = (InputDevice::Unknown, 0);
+        for device in [
+            InputDevice::Keyboard,
+            InputDevice::Mouse,
+            InputDevice::Gamepad,
+        ] {
+            let count = counts.get(&device).copied().unwrap_or_default();
+            if count > primary_device.1 {
+                primary_device = (device, count);
+            }
+        }
+        primary_device.0
+    }
+
+    /// Counts how many events of each [`InputEventKind`] are stored.
+    ///
+    /// Kinds without any events are omitted.
+    pub fn count_by_kind(&self) -> HashMap<InputEventKind, usize> {
+        let mut counts = HashMap::default();
+        for event in self.events.iter() {
+            *counts
+                .entry(InputEventKind::from(&event.input_event))
+                .or_default() += 1;
+        }
+        counts
+    }
+
+    /// Counts how many events of each [`InputEventKind`] occurred in each consecutive `bucket` of time since startup.
+    ///
+    /// Buckets are aligned to multiples of `bucket`, beginning with the bucket of the earliest event.
+    /// Empty buckets between events are included.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bucket` is [`Duration::ZERO`], or if the events span more buckets than can be allocated.
+    pub fn histogram(&self, bucket: Duration) -> Vec<BucketCounts> {
+        assert!(!bucket.is_zero(), "The bucket size must be non-zero.");
+
+        // Rounds down to the start of the containing bucket, without overflowing on long recordings
+        let bucket_start = |time: Duration| {
+            let offset = time.as_nanos() % bucket.as_nanos();
+            time - Duration::new(
+                (offset / 1_000_000_000) as u64,
+                (offset % 1_000_000_000) as u32,
+            )
+        };
+        let Some(first_start) = self
+            .events
+            .iter()
+            .map(|event| bucket_start(event.time_since_startup))
+            .min()
+        else {
+            return Vec::new();
+        };
+
+        let mut buckets: Vec<BucketCounts> = Vec::new();
+        for event in self.events.iter() {
+            let index = (bucket_start(event.time_since_startup) - first_start).as_nanos()
+                / bucket.as_nanos();
+            let index = usize::try_from(index).expect("Too many buckets to allocate.");
+            while buckets.len() <= index {
+                let start = buckets
+                    .last()
+                    .map_or(first_start, |previous| previous.start + bucket);
+                buckets.push(BucketCounts {
+                    start,
+                    ..Default::default()
+                });
+            }
+            *buckets[index]
+                .counts
+                .entry(InputEventKind::from(&event.input_event))
+                .or_default() += 1;
+        }
+        buckets
+    }
+
+    /// The number of actions per minute (APM) over the duration of the recording.
+    ///
+    /// Every press of a keyboard key, mouse button or gamepad button counts as an action,
+    /// but repeated key presses from the operating system's key repeat are ignored.
+    /// Returns `0.0` if the recording is empty or all of its events were sent at the same time.
+    pub fn actions_per_minute(&self) -> f32 {
+        let actions = self
+            .events
+            .iter()
+            .filter(|event| {
+                !matches!(&event.input_event, InputEvent::Keyboard(event) if event.repeat)
+                    && matches!(
+                        event.input_event.buttonlike_state(),
+                        Some((_, ButtonState::Pressed))
+                    )
+            })
+            .count();
+        self.rate_per_second(actions) * 60.0
+    }
+
+    /// The number of mouse button presses per second (CPS) over the duration of the recording.
+    ///
+    /// Returns `0.0` if the recording is empty or all of its events were sent at the same time.
+    pub fn clicks_per_second(&self) -> f32 {
+        let clicks = self
+            .events
+            .iter()
+            .filter(|event| {
+                matches!(
+                    event.input_event.buttonlike_state(),
+                    Some((ButtonlikeInput::MouseButton(_), ButtonState::Pressed))
+                )
+            })
+            .count();
+        self.rate_per_second(clicks)
+    }
+
+    /// Divides `count` by the time between the earliest and latest events, in seconds.
+    fn rate_per_second(&self, count: usize) -> f32 {
+        let times = self.events.iter().map(|event| event.time_since_startup);
+        let (Some(start), Some(end)) = (times.clone().min(), times.max()) else {
+            return 0.0;
+        };
+        let span = (end - start).as_secs_f32();
+        if span > 0.0 {
+            count as f32 / span
+        } else {
+            0.0
+        }
+    }
+
+    /// Removes events until roughly `target` events remain, preferring to drop lower-priority events.
+    ///
+    /// Continuous inputs ([`InputEvent::CursorMoved`], [`InputEvent::MouseWheel`] and gamepad axis events) are dropped first.
+    /// If that is not enough, buttonlike inputs are dropped as whole press-release spans,
+    /// so that no press is ever orphaned from its release.
+    /// Events are dropped evenly across the recording, and all other events are always kept.
+    ///
+    /// The `cursor` is adjusted so that it continues to point to the same unread event.
+    pub fn downsample(&mut self, target: usize) {
+        if self.events.len() <= target {
+            return;
+        }
+
+        let mut excess = self.events.len() - target;
+        let mut keep = vec![true; self.events.len()];
+
+        let continuous: Vec<usize> = self
+            .events
+            .iter()
+            .enumerate()
+            .filter(|(_, event)| event.input_event.is_continuous())
+            .map(|(index, _)| index)
+            .collect();
+        let n_dropped = excess.min(continuous.len());
+        for index in evenly_spaced(continuous.len(), n_dropped) {
+            keep[continuous[index]] = false;
+        }
+        excess -= n_dropped;
+
+        // Each span contains at least a press and its release
+        let spans = self.held_spans();
+        let n_dropped = excess.div_ceil(2).min(spans.len());
+        for index in evenly_spaced(spans.len(), n_dropped) {
+            for &event_index in spans[index].iter() {
+                keep[event_index] = false;
+            }
+        }
+
+        self.cursor = keep[..self.cursor].iter().filter(|kept| **kept).count();
+        let mut keep = keep.into_iter();
+        self.events.retain(|_| keep.next().unwrap_or(true));
+    }
+
+    /// Finds the indexes of each press of a buttonlike input, any repeated presses, and its matching release.
+    ///
+    /// Presses that are never released (and releases that were never pressed) are not included.
+    fn held_spans(&self) -> Vec<Vec<usize>> {
+        let mut open_spans: HashMap<ButtonlikeInput, Vec<usize>> = HashMap::default();
+        let mut spans = Vec::new();
+
+        for (index, event) in self.events.iter().enumerate() {
+            let Some((input, state)) = event.input_event.buttonlike_state() else {
+                continue;
+            };
+
+            match state {
+                ButtonState::Pressed => open_spans.entry(input).or_default().push(index),
+                ButtonState::Released => {
+                    if let Some(mut span) = open_spans.remove(&input) {
+                        span.push(index);
+                        spans.push(span);
+                    }
+                }
+            }
+        }
+
+        // Sort by the initial press, rather than by the release
+        spans.sort_by_key(|span| span[0]);
+        spans
+    }
+
+    /// Finds the buttonlike inputs held at the end of each frame in which that set of held inputs changes.
+    fn held_state_changes(&self) -> Vec<(FrameCount, HashSet<ButtonlikeInput>)> {
+        let mut held = HashSet::default();
+        let mut changes: Vec<(FrameCount, HashSet<ButtonlikeInput>)> = Vec::new();
+
+        for (frame, input_events) in self.per_frame_snapshots() {
+            for input_event in input_events {
+                match input_event.buttonlike_state() {
+                    Some((input, ButtonState::Pressed)) => {
+                        held.insert(input);
+                    }
+                    Some((input, ButtonState::Released)) => {
+                        held.remove(&input);
+                    }
+                    None => (),
+                }
+            }
+
+            let previous = changes.last().map(|(_, previous)| previous);
+            if previous.unwrap_or(&HashSet::default()) != &held {
+                changes.push((frame, held.clone()));
+            }
+        }
+        changes
+    }
+
+    /// Finds the keyboard, mouse button and gamepad button events that are not balanced by a matching press or release.
+    ///
+    /// This includes presses that are never released, which would leave the input stuck on replay,
+    /// as well as releases of inputs that were never pressed.
+    /// For inputs that are pressed repeatedly without a release, only the first press is reported.
+    /// Events are returned in the order they were recorded.
+    pub fn unbalanced_inputs(&self) -> Vec<InputEvent> {
+        let mut open_presses: HashMap<ButtonlikeInput, usize> = HashMap::default();
+        let mut unbalanced = Vec::new();
+
+        for (index, event) in self.events.iter().enumerate() {
+            let Some((input, state)) = event.input_event.buttonlike_state() else {
+                continue;
+            };
+
+            match state {
+                ButtonState::Pressed => {
+                    open_presses.entry(input).or_insert(index);
+                }
+                ButtonState::Released => {
+                    if open_presses.remove(&input).is_none() {
+                        unbalanced.push(index);
+                    }
+                }
+            }
+        }
+
+        unbalanced.extend(open_presses.into_values());
+        unbalanced.sort_unstable();
+        unbalanced
+            .into_iter()
+            .map(|index| self.events[index].input_event.clone())
+            .collect()
+    }
+
+    /// Appends a synthetic release for every keyboard, mouse button and gamepad button input that is left pressed at the end of the recording.
+    ///
+    /// The releases are sent at the frame and time of the last event, so that replays do not leave inputs stuck.
+    /// Releases of inputs that were never pressed are left untouched.
+    pub fn balance_inputs(&mut self) {
+        let Some(last) = self.events.last() else {
+            return;
+        };
+        let (frame, time_since_startup) = (last.frame, last.time_since_startup);
+
+        let mut open_presses: HashMap<ButtonlikeInput, usize> = HashMap::default();
+        for (index, event) in self.events.iter().enumerate() {
+            match event.input_event.buttonlike_state() {
+                Some((input, ButtonState::Pressed)) => {
+                    open_presses.insert(input, index);
+                }
+                Some((input, ButtonState::Released)) => {
+                    open_presses.remove(&input);
+                }
+                None => (),
+            }
+        }
+
+        let mut press_indexes: Vec<usize> = open_presses.into_values().collect();
+        press_indexes.sort_unstable();
+        for index in press_indexes {
+            if let Some(release) = self.events[index].input_event.released() {
+                self.send(frame, time_since_startup, release);
+            }
+        }
+    }
+
     /// Gets the total length of the event stream
     pub fn len(&self) -> usize {
         self.events.len()
@@ -173,6 +1010,59 @@ impl TimestampedInputs {
         result
     }
 
+    /// Returns an iterator over all recorded events up to and including the provided `frame`, beginning at the current `cursor`.
+    ///
+    /// Iteration also stops at the first [`InputEvent::IdleMarker`] recorded after `time_since_startup`,
+    /// so that idle periods are not played back faster than they were recorded.
+    /// This method should only be used on [`TimestampedInputs`] resources that are sorted by [`SortingStrategy::FrameCount`].
+    #[must_use]
+    pub fn iter_until_frame_or_idle_marker(
+        &mut self,
+        frame: FrameCount,
+        time_since_startup: Duration,
+    ) -> impl IntoIterator<Item = TimestampedInputEvent> {
+        debug_assert!(TimestampedInputs::is_sorted(
+            self,
+            SortingStrategy::FrameCount
+        ));
+        let mut result = Vec::with_capacity(self.events.len() - self.cursor);
+        while let Some(event) = self.events.get(self.cursor) {
+            let is_early_idle_marker = event.input_event == InputEvent::IdleMarker
+                && event.time_since_startup > time_since_startup;
+            if event.frame > frame || is_early_idle_marker {
+                break;
+            }
+            result.push(event.clone());
+            self.cursor += 1;
+        }
+        result
+    }
+
+    /// Returns an iterator over all recorded events up to and including both the provided `frame` and `time_since_startup`, beginning at the current `cursor`.
+    ///
+    /// Iteration stops at the first event past either limit.
+    /// This method should only be used on [`TimestampedInputs`] resources that are sorted by [`SortingStrategy::FrameCount`].
+    #[must_use]
+    pub fn iter_until_frame_and_time(
+        &mut self,
+        frame: FrameCount,
+        time_since_startup: Duration,
+    ) -> impl IntoIterator<Item = TimestampedInputEvent> {
+        debug_assert!(TimestampedInputs::is_sorted(
+            self,
+            SortingStrategy::FrameCount
+        ));
+        let mut result = Vec::with_capacity(self.events.len() - self.cursor);
+        while self.cursor < self.events.len()
+            && self.events[self.cursor].frame <= frame
+            && self.events[self.cursor].time_since_startup <= time_since_startup
+        {
+            result.push(self.events[self.cursor].clone());
+            self.cursor += 1;
+        }
+        result
+    }
+
     /// Returns an iterator over recorded events starting from (inclusive) the start time,
     /// and until (exclusive) the end time.
     ///
@@ -290,7 +1180,9 @@ impl TimestampedInputs {
         Some(last_read.input_event.clone())
     }
 
-    /// The frame count of the next event to read.
+    /// The [`InputEvent`] of the next event to read.
+    ///
+    /// This clones the event: use [`current_time`](Self::current_time) or [`current_framecount`](Self::current_framecount) to cheaply peek at when it occurs.
     pub fn current_input(&self) -> Option<InputEvent> {
         let next_read = self.events.get(self.cursor)?;
         Some(next_read.input_event.clone())
@@ -327,6 +1219,15 @@ impl TimestampedInputs {
         let next_read = self.events.get(self.cursor)?;
         Some(next_read.time_since_startup)
     }
+
+    /// The time between the last-read event and the next event to read, without advancing the cursor.
+    ///
+    /// If no event has been read yet, the gap is measured from startup.
+    /// Returns `None` once every event has been read.
+    pub fn next_event_gap(&self) -> Option<Duration> {
+        let next_time = self.current_time()?;
+        Some(next_time.saturating_sub(self.last_time().unwrap_or_default()))
+    }
 }
 
 impl Iterator for TimestampedInputs {
@@ -341,6 +1242,39 @@ impl Iterator for TimestampedInputs {
     }
 }
 
+/// Returns `n_chosen` distinct indexes, evenly spread across `0..len`.
+fn evenly_spaced(len: usize, n_chosen: usize) -> impl Iterator<Item = usize> {
+    debug_assert!(n_chosen <= len);
+    (0..n_chosen).map(move |i| i * len / n_chosen)
+}
+
+/// An item of [`TimestampedInputs::iter_with_waits`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlaybackStep {
+    /// Wait for this long before the next item.
+    Wait(Duration),
+    /// Play back this event.
+    Event(TimestampedInputEvent),
+}
+
+/// The difference between two versions of a recording, created by [`TimestampedInputs::diff_patch`].
+///
+/// Only the events between the longest shared prefix and suffix of the two recordings are stored,
+/// so many similar variants of a recording can be saved compactly alongside a single base recording.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordingPatch {
+    /// The header of the modified recording.
+    pub header: RecordingHeader,
+    /// The number of events in the base recording.
+    pub base_len: usize,
+    /// The number of events at the start of the base recording that are kept.
+    pub prefix_len: usize,
+    /// The number of events at the end of the base recording that are kept.
+    pub suffix_len: usize,
+    /// The events that replace the rest of the base recording.
+    pub events: Vec<TimestampedInputEvent>,
+}
+
 /// The sorting strategy used for the [`TimestampedInputs::sort`] method.
 ///
 /// In all typical cases, these two sorting strategies should agree.
@@ -351,6 +1285,45 @@ pub enum SortingStrategy {
     TimeSinceStartup,
 }
 
+/// How conflicting events are resolved by [`TimestampedInputs::merge_with`].
+///
+/// Events conflict when they occur on the same [`FrameCount`] and have the same [`InputEventKind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergePolicy {
+    /// Keep the events from both recordings
+    #[default]
+    KeepBoth,
+    /// Keep only the events from the recording being merged into
+    PreferSelf,
+    /// Keep only the events from the recording being merged in
+    PreferOther,
+}
+
+/// The number of events of each kind that occurred in a single bucket of time.
+///
+/// Generated by [`TimestampedInputs::histogram`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BucketCounts {
+    /// The time since startup at which this bucket begins
+    pub start: Duration,
+    /// The number of events of each [`InputEventKind`] in this bucket
+    ///
+    /// Kinds without any events are omitted.
+    pub counts: HashMap<InputEventKind, usize>,
+}
+
+impl BucketCounts {
+    /// The number of events of the provided `kind` in this bucket
+    pub fn count(&self, kind: InputEventKind) -> usize {
+        self.counts.get(&kind).copied().unwrap_or_default()
+    }
+
+    /// The total number of events in this bucket, regardless of kind
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+}
+
 /// Collects input-relevant events for use in [`TimestampedInputs`]
 // BLOCKED: this should be PartialEq, but we're blocked on https://github.com/bevyengine/bevy/issues/6024
 #[allow(missing_docs)]
@@ -362,6 +1335,246 @@ pub enum InputEvent {
     CursorMoved(CursorMoved),
     Gamepad(GamepadEvent),
     AppExit,
+    /// A no-op marker recorded while no other inputs were captured, see [`IdleMarkers`](crate::input_capture::IdleMarkers).
+    ///
+    /// Nothing is sent when this event is played back.
+    IdleMarker,
+    /// A snapshot of the [`AccumulatedMouseMotion`] resource, see [`CaptureAccumulatedMouse`](crate::input_capture::CaptureAccumulatedMouse).
+    ///
+    /// The resource is restored directly when this event is played back.
+    AccumulatedMouseMotion(AccumulatedMouseMotion),
+    /// A snapshot of the [`AccumulatedMouseScroll`] resource, see [`CaptureAccumulatedMouse`](crate::input_capture::CaptureAccumulatedMouse).
+    ///
+    /// The resource is restored directly when this event is played back.
+    AccumulatedMouseScroll(AccumulatedMouseScroll),
+    /// A [`GamepadRumbleRequest`] sent by the app, see [`RumbleRequest`].
+    GamepadRumble(RumbleRequest),
+    /// A `bevy::picking` pointer event, see [`PointerEvent`](crate::picking::PointerEvent).
+    ///
+    /// These can be loaded by any build, but are only captured and replayed with the `picking` feature.
+    Pointer(crate::picking::PointerEvent),
+    /// A [`WindowMoved`] event.
+    ///
+    /// When played back, the [`Window::position`](bevy::window::Window::position) of the recorded window is updated to match.
+    WindowMoved(WindowMoved),
+    /// An [`Ime`] event, such as the text committed by an input method or pasted into a window.
+    ///
+    /// Committed text is stored as a single event, so that it is replayed in one frame with its characters in order.
+    Ime(Ime),
+}
+
+/// A serializable copy of a [`GamepadRumbleRequest`], as stored in [`InputEvent::GamepadRumble`].
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum RumbleRequest {
+    /// See [`GamepadRumbleRequest::Add`]
+    Add {
+        duration: Duration,
+        strong_motor: f32,
+        weak_motor: f32,
+        gamepad: Entity,
+    },
+    /// See [`GamepadRumbleRequest::Stop`]
+    Stop { gamepad: Entity },
+}
+
+impl RumbleRequest {
+    /// The gamepad entity that this request targets.
+    pub fn gamepad(&self) -> Entity {
+        match self {
+            RumbleRequest::Add { gamepad, .. } | RumbleRequest::Stop { gamepad } => *gamepad,
+        }
+    }
+}
+
+impl From<GamepadRumbleRequest> for RumbleRequest {
+    fn from(request: GamepadRumbleRequest) -> Self {
+        match request {
+            GamepadRumbleRequest::Add {
+                duration,
+                intensity,
+                gamepad,
+            } => RumbleRequest::Add {
+                duration,
+                strong_motor: intensity.strong_motor,
+                weak_motor: intensity.weak_motor,
+                gamepad,
+            },
+            GamepadRumbleRequest::Stop { gamepad } => RumbleRequest::Stop { gamepad },
+        }
+    }
+}
+
+impl From<RumbleRequest> for GamepadRumbleRequest {
+    fn from(request: RumbleRequest) -> Self {
+        match request {
+            RumbleRequest::Add {
+                duration,
+                strong_motor,
+                weak_motor,
+                gamepad,
+            } => GamepadRumbleRequest::Add {
+                duration,
+                intensity: GamepadRumbleIntensity {
+                    strong_motor,
+                    weak_motor,
+                },
+                gamepad,
+            },
+            RumbleRequest::Stop { gamepad } => GamepadRumbleRequest::Stop { gamepad },
+        }
+    }
+}
+
+/// The kind of an [`InputEvent`], without any of its data.
+///
+/// Kinds are ordered as they are declared.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum InputEventKind {
+    Keyboard,
+    MouseButton,
+    MouseWheel,
+    CursorMoved,
+    Gamepad,
+    AppExit,
+    IdleMarker,
+    AccumulatedMouseMotion,
+    AccumulatedMouseScroll,
+    GamepadRumble,
+    Pointer,
+    WindowMoved,
+    Ime,
+}
+
+impl InputEventKind {
+    /// The [`InputDevice`] that produces events of this kind, if any.
+    ///
+    /// Gamepad rumble requests are sent to a gamepad rather than produced by it, and so have no device.
+    pub fn device(&self) -> Option<InputDevice> {
+        match self {
+            InputEventKind::Keyboard | InputEventKind::Ime => Some(InputDevice::Keyboard),
+            InputEventKind::MouseButton
+            | InputEventKind::MouseWheel
+            | InputEventKind::CursorMoved
+            | InputEventKind::AccumulatedMouseMotion
+            | InputEventKind::AccumulatedMouseScroll => Some(InputDevice::Mouse),
+            InputEventKind::Pointer => Some(InputDevice::Mouse),
+            InputEventKind::Gamepad => Some(InputDevice::Gamepad),
+            InputEventKind::AppExit
+            | InputEventKind::IdleMarker
+            | InputEventKind::GamepadRumble
+            | InputEventKind::WindowMoved => None,
+        }
+    }
+}
+
+/// A physical device that produces input events, see [`TimestampedInputs::primary_device`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputDevice {
+    /// A keyboard, including text entered through an input method editor.
+    Keyboard,
+    /// A mouse, including its buttons, wheel and cursor.
+    Mouse,
+    /// Any gamepad.
+    Gamepad,
+    /// No device could be identified, as the recording contains no events from any device.
+    Unknown,
+}
+
+impl From<&InputEvent> for InputEventKind {
+    fn from(event: &InputEvent) -> Self {
+        match event {
+            InputEvent::Keyboard(_) => InputEventKind::Keyboard,
+            InputEvent::MouseButton(_) => InputEventKind::MouseButton,
+            InputEvent::MouseWheel(_) => InputEventKind::MouseWheel,
+            InputEvent::CursorMoved(_) => InputEventKind::CursorMoved,
+            InputEvent::Gamepad(_) => InputEventKind::Gamepad,
+            InputEvent::AppExit => InputEventKind::AppExit,
+            InputEvent::IdleMarker => InputEventKind::IdleMarker,
+            InputEvent::AccumulatedMouseMotion(_) => InputEventKind::AccumulatedMouseMotion,
+            InputEvent::AccumulatedMouseScroll(_) => InputEventKind::AccumulatedMouseScroll,
+            InputEvent::GamepadRumble(_) => InputEventKind::GamepadRumble,
+            InputEvent::Pointer(_) => InputEventKind::Pointer,
+            InputEvent::WindowMoved(_) => InputEventKind::WindowMoved,
+            InputEvent::Ime(_) => InputEventKind::Ime,
+        }
+    }
+}
+
+impl InputEvent {
+    /// Is this event a continuous input, where individual events can be dropped without leaving inputs stuck?
+    fn is_continuous(&self) -> bool {
+        matches!(
+            self,
+            InputEvent::CursorMoved(_)
+                | InputEvent::MouseWheel(_)
+                | InputEvent::Gamepad(GamepadEvent::Axis(_))
+                | InputEvent::AccumulatedMouseMotion(_)
+                | InputEvent::AccumulatedMouseScroll(_)
+                | InputEvent::WindowMoved(_)
+                | InputEvent::GamepadRumble(RumbleRequest::Add { .. })
+        )
+    }
+
+    /// The gamepad entity that this event was sent by, if this is a gamepad event.
+    fn gamepad(&self) -> Option<Entity> {
+        match self {
+            InputEvent::Gamepad(GamepadEvent::Connection(event)) => Some(event.gamepad),
+            InputEvent::Gamepad(GamepadEvent::Button(event)) => Some(event.entity),
+            InputEvent::Gamepad(GamepadEvent::Axis(event)) => Some(event.entity),
+            InputEvent::GamepadRumble(request) => Some(request.gamepad()),
+            _ => None,
+        }
+    }
+
+    /// A copy of this buttonlike input event that releases the button, if this is a buttonlike input event.
+    fn released(&self) -> Option<InputEvent> {
+        match self {
+            InputEvent::Keyboard(event) => Some(InputEvent::Keyboard(KeyboardInput {
+                state: ButtonState::Released,
+                repeat: false,
+                ..event.clone()
+            })),
+            InputEvent::MouseButton(event) => Some(InputEvent::MouseButton(MouseButtonInput {
+                state: ButtonState::Released,
+                ..*event
+            })),
+            InputEvent::Gamepad(GamepadEvent::Button(event)) => Some(InputEvent::Gamepad(
+                GamepadEvent::Button(GamepadButtonChangedEvent {
+                    state: ButtonState::Released,
+                    value: 0.0,
+                    ..*event
+                }),
+            )),
+            _ => None,
+        }
+    }
+
+    /// The buttonlike input whose state this event changes, along with its new state.
+    pub(crate) fn buttonlike_state(&self) -> Option<(ButtonlikeInput, ButtonState)> {
+        match self {
+            InputEvent::Keyboard(event) => {
+                Some((ButtonlikeInput::Keyboard(event.key_code), event.state))
+            }
+            InputEvent::MouseButton(event) => {
+                Some((ButtonlikeInput::MouseButton(event.button), event.state))
+            }
+            InputEvent::Gamepad(GamepadEvent::Button(event)) => Some((
+                ButtonlikeInput::GamepadButton(event.entity, event.button),
+                event.state,
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Identifies a single buttonlike input, so that presses can be matched with their releases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum ButtonlikeInput {
+    Keyboard(KeyCode),
+    MouseButton(MouseButton),
+    GamepadButton(Entity, GamepadButton),
 }
 
 impl From<KeyboardInput> for InputEvent {
@@ -388,6 +1601,18 @@ impl From<CursorMoved> for InputEvent {
     }
 }
 
+impl From<WindowMoved> for InputEvent {
+    fn from(event: WindowMoved) -> Self {
+        InputEvent::WindowMoved(event)
+    }
+}
+
+impl From<Ime> for InputEvent {
+    fn from(event: Ime) -> Self {
+        InputEvent::Ime(event)
+    }
+}
+
 impl From<GamepadEvent> for InputEvent {
     fn from(event: GamepadEvent) -> Self {
         InputEvent::Gamepad(event)
@@ -400,11 +1625,29 @@ impl From<AppExit> for InputEvent {
     }
 }
 
+impl From<GamepadRumbleRequest> for InputEvent {
+    fn from(request: GamepadRumbleRequest) -> Self {
+        InputEvent::GamepadRumble(request.into())
+    }
+}
+
+impl From<AccumulatedMouseMotion> for InputEvent {
+    fn from(resource: AccumulatedMouseMotion) -> Self {
+        InputEvent::AccumulatedMouseMotion(resource)
+    }
+}
+
+impl From<AccumulatedMouseScroll> for InputEvent {
+    fn from(resource: AccumulatedMouseScroll) -> Self {
+        InputEvent::AccumulatedMouseScroll(resource)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use bevy::input::mouse::MouseButton;
-    use bevy::input::ButtonState;
+    use bevy::math::Vec2;
+    use bevy::window::CursorMoved;
 
     const LEFT_CLICK_PRESS: InputEvent = InputEvent::MouseButton(MouseButtonInput {
         button: MouseButton::Left,
@@ -580,6 +1823,551 @@ mod tests {
         assert_eq!(iter.into_iter().count(), 3);
     }
 
+    #[test]
+    fn histogram() {
+        let mut timestamped_input = complex_timestamped_input();
+        timestamped_input.send(FrameCount(4), Duration::from_secs(6), InputEvent::AppExit);
+
+        let buckets = timestamped_input.histogram(Duration::from_secs(2));
+        assert_eq!(buckets.len(), 4);
+
+        assert_eq!(buckets[0].start, Duration::from_secs(0));
+        assert_eq!(buckets[0].count(InputEventKind::MouseButton), 2);
+        assert_eq!(buckets[0].total(), 2);
+
+        assert_eq!(buckets[1].start, Duration::from_secs(2));
+        assert_eq!(buckets[1].count(InputEventKind::MouseButton), 3);
+        assert_eq!(buckets[1].total(), 3);
+
+        // No events occurred between 4 and 6 seconds
+        assert_eq!(buckets[2].start, Duration::from_secs(4));
+        assert_eq!(buckets[2].total(), 0);
+
+        assert_eq!(buckets[3].count(InputEventKind::AppExit), 1);
+        assert_eq!(buckets[3].total(), 1);
+    }
+
+    #[test]
+    fn histogram_starts_at_first_event() {
+        let mut timestamped_input = TimestampedInputs::default();
+        timestamped_input.send(
+            FrameCount(1),
+            Duration::from_secs(3600) + Duration::from_micros(2500),
+            LEFT_CLICK_PRESS,
+        );
+        timestamped_input.send(
+            FrameCount(2),
+            Duration::from_secs(3600) + Duration::from_micros(4500),
+            LEFT_CLICK_PRESS,
+        );
+
+        // Buckets before the first event are not allocated
+        let buckets = timestamped_input.histogram(Duration::from_millis(1));
+        let starts: Vec<Duration> = buckets.iter().map(|bucket| bucket.start).collect();
+        assert_eq!(
+            starts,
+            [2, 3, 4]
+                .map(|millis| Duration::from_secs(3600) + Duration::from_millis(millis))
+                .to_vec()
+        );
+        let totals: Vec<usize> = buckets.iter().map(BucketCounts::total).collect();
+        assert_eq!(totals, vec![1, 0, 1]);
+    }
+
+    #[test]
+    fn seek_to_first_matching() {
+        let mut timestamped_input = TimestampedInputs::default();
+        let cursor_moved = CursorMoved {
+            window: Entity::PLACEHOLDER,
+            position: Vec2::ZERO,
+            delta: None,
+        };
+        timestamped_input.send(FrameCount(0), Duration::ZERO, cursor_moved.clone().into());
+        timestamped_input.send(FrameCount(1), Duration::ZERO, cursor_moved.into());
+        timestamped_input.send(FrameCount(2), Duration::ZERO, LEFT_CLICK_PRESS);
+        timestamped_input.send(FrameCount(3), Duration::ZERO, LEFT_CLICK_RELEASE);
+
+        let is_mouse_button =
+            |event: &TimestampedInputEvent| matches!(event.input_event, InputEvent::MouseButton(_));
+
+        assert_eq!(
+            timestamped_input.seek_to_first_matching(is_mouse_button),
+            Some(2)
+        );
+        assert_eq!(timestamped_input.current_input(), Some(LEFT_CLICK_PRESS));
+        assert_eq!(timestamped_input.current_framecount(), Some(FrameCount(2)));
+
+        // Seeking only considers unread events
+        timestamped_input.next();
+        assert_eq!(
+            timestamped_input.seek_to_first_matching(is_mouse_button),
+            Some(3)
+        );
+
+        let is_app_exit =
+            |event: &TimestampedInputEvent| matches!(event.input_event, InputEvent::AppExit);
+        assert_eq!(timestamped_input.seek_to_first_matching(is_app_exit), None);
+        assert_eq!(timestamped_input.cursor, 3);
+    }
+
+    #[test]
+    fn from_timed() {
+        use bevy::input::keyboard::Key;
+
+        let key_event = |state| KeyboardInput {
+            logical_key: Key::Space,
+            key_code: KeyCode::Space,
+            state,
+            window: Entity::PLACEHOLDER,
+            repeat: false,
+        };
+
+        let timestamped_input = TimestampedInputs::from_timed([
+            (
+                FrameCount(0),
+                Duration::ZERO,
+                key_event(ButtonState::Pressed),
+            ),
+            (
+                FrameCount(3),
+                Duration::from_millis(50),
+                key_event(ButtonState::Released),
+            ),
+        ]);
+
+        assert_eq!(timestamped_input.len(), 2);
+        assert_eq!(timestamped_input.cursor, 0);
+        assert_eq!(
+            timestamped_input.events[1],
+            TimestampedInputEvent {
+                frame: FrameCount(3),
+                time_since_startup: Duration::from_millis(50),
+                input_event: InputEvent::Keyboard(key_event(ButtonState::Released)),
+                precise_time: None,
+                modifiers: None,
+            }
+        );
+    }
+
+    #[test]
+    fn splice_range() {
+        let right_click_press = InputEvent::MouseButton(MouseButtonInput {
+            button: MouseButton::Right,
+            state: ButtonState::Pressed,
+            window: Entity::PLACEHOLDER,
+        });
+        let right_click_release = InputEvent::MouseButton(MouseButtonInput {
+            button: MouseButton::Right,
+            state: ButtonState::Released,
+            window: Entity::PLACEHOLDER,
+        });
+        let mut replacement = TimestampedInputs::default();
+        replacement.send(
+            FrameCount(10),
+            Duration::from_secs(10),
+            right_click_press.clone(),
+        );
+        replacement.send(
+            FrameCount(11),
+            Duration::from_secs(11),
+            right_click_release.clone(),
+        );
+        let shift = Modifiers {
+            shift: true,
+            ..Default::default()
+        };
+        replacement.events[0].modifiers = Some(shift);
+
+        let mut timestamped_input = complex_timestamped_input();
+        timestamped_input.next();
+        timestamped_input.splice_range(FrameCount(1)..FrameCount(3), replacement);
+
+        let expected = [
+            (FrameCount(0), Duration::from_secs(0), LEFT_CLICK_PRESS),
+            (FrameCount(1), Duration::from_secs(1), right_click_press),
+            (FrameCount(2), Duration::from_secs(2), right_click_release),
+            (FrameCount(3), Duration::from_secs(3), LEFT_CLICK_PRESS),
+        ];
+        assert_eq!(timestamped_input.len(), expected.len());
+        for (event, (frame, time_since_startup, input_event)) in
+            timestamped_input.events.iter().zip(expected)
+        {
+            assert_eq!(event.frame, frame);
+            assert_eq!(event.time_since_startup, time_since_startup);
+            assert_eq!(event.input_event, input_event);
+        }
+        // Modifier state belongs to each event, so it is kept
+        let modifiers: Vec<Option<Modifiers>> = timestamped_input
+            .events
+            .iter()
+            .map(|event| event.modifiers)
+            .collect();
+        assert_eq!(modifiers, vec![None, Some(shift), None, None]);
+        assert_eq!(timestamped_input.cursor, 0);
+    }
+
+    #[test]
+    fn splice_into_empty_range() {
+        let mut replacement = TimestampedInputs::default();
+        replacement.send(FrameCount(7), Duration::from_secs(7), LEFT_CLICK_PRESS);
+
+        let mut timestamped_input = TimestampedInputs::default();
+        timestamped_input.send(FrameCount(1), Duration::from_secs(1), LEFT_CLICK_PRESS);
+        timestamped_input.send(FrameCount(10), Duration::from_secs(10), LEFT_CLICK_PRESS);
+        timestamped_input.splice_range(FrameCount(3)..FrameCount(5), replacement);
+
+        // Nothing was replaced, so the replacement is anchored to the last event before the range
+        let frames_and_times: Vec<(FrameCount, Duration)> = timestamped_input
+            .events
+            .iter()
+            .map(|event| (event.frame, event.time_since_startup))
+            .collect();
+        assert_eq!(
+            frames_and_times,
+            vec![
+                (FrameCount(1), Duration::from_secs(1)),
+                (FrameCount(3), Duration::from_secs(1)),
+                (FrameCount(10), Duration::from_secs(10)),
+            ]
+        );
+    }
+
+    #[test]
+    fn iter_with_waits() {
+        let mut timestamped_input = TimestampedInputs::default();
+        timestamped_input.send(FrameCount(0), Duration::ZERO, LEFT_CLICK_PRESS);
+        timestamped_input.send(FrameCount(1), Duration::from_secs(1), LEFT_CLICK_RELEASE);
+        timestamped_input.send(FrameCount(1), Duration::from_secs(1), LEFT_CLICK_PRESS);
+        timestamped_input.send(FrameCount(2), Duration::from_secs(3), LEFT_CLICK_RELEASE);
+
+        let steps: Vec<PlaybackStep> = timestamped_input.iter_with_waits().collect();
+        let events = &timestamped_input.events;
+        assert_eq!(
+            steps,
+            vec![
+                PlaybackStep::Event(events[0].clone()),
+                PlaybackStep::Wait(Duration::from_secs(1)),
+                PlaybackStep::Event(events[1].clone()),
+                PlaybackStep::Event(events[2].clone()),
+                PlaybackStep::Wait(Duration::from_secs(2)),
+                PlaybackStep::Event(events[3].clone()),
+            ]
+        );
+    }
+
+    #[test]
+    fn patch_round_trip() {
+        let mut base = TimestampedInputs::default();
+        for frame in 0..10 {
+            base.send(FrameCount(frame), Duration::ZERO, LEFT_CLICK_PRESS);
+        }
+
+        let mut modified = base.clone();
+        modified.events[4].input_event = LEFT_CLICK_RELEASE;
+        modified.events.remove(6);
+        modified
+            .header
+            .metadata
+            .insert("variant".into(), "b".into());
+
+        let patch = TimestampedInputs::diff_patch(&base, &modified);
+        assert_eq!((patch.prefix_len, patch.suffix_len), (4, 3));
+        assert_eq!(patch.events.len(), 2);
+        assert_eq!(TimestampedInputs::apply_patch(&base, &patch), modified);
+
+        #[cfg(feature = "file")]
+        {
+            let serialized = ron::ser::to_string(&patch).unwrap();
+            assert_eq!(
+                ron::de::from_str::<RecordingPatch>(&serialized).unwrap(),
+                patch
+            );
+        }
+
+        // Recordings that only differ in length share as much as possible
+        let mut shortened = base.clone();
+        shortened.events.truncate(5);
+        let patch = TimestampedInputs::diff_patch(&base, &shortened);
+        assert!(patch.events.is_empty());
+        assert_eq!(TimestampedInputs::apply_patch(&base, &patch), shortened);
+
+        let unchanged = TimestampedInputs::diff_patch(&base, &base);
+        assert_eq!(TimestampedInputs::apply_patch(&base, &unchanged), base);
+    }
+
+    #[test]
+    fn canonicalize() {
+        let key_press = InputEvent::Keyboard(KeyboardInput {
+            key_code: KeyCode::KeyF,
+            logical_key: bevy::input::keyboard::Key::Character("f".into()),
+            state: ButtonState::Pressed,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        });
+
+        let mut timestamped_input = TimestampedInputs::default();
+        timestamped_input.send(FrameCount(1), Duration::from_secs(1), LEFT_CLICK_RELEASE);
+        timestamped_input.send(FrameCount(0), Duration::ZERO, LEFT_CLICK_PRESS);
+        timestamped_input.send(FrameCount(0), Duration::ZERO, LEFT_CLICK_PRESS);
+        timestamped_input.send(FrameCount(0), Duration::ZERO, key_press.clone());
+        timestamped_input.cursor = 2;
+
+        timestamped_input.canonicalize();
+        let input_events: Vec<InputEvent> = timestamped_input
+            .events
+            .iter()
+            .map(|event| event.input_event.clone())
+            .collect();
+        assert_eq!(
+            input_events,
+            vec![key_press, LEFT_CLICK_PRESS, LEFT_CLICK_RELEASE]
+        );
+        assert_eq!(timestamped_input.cursor, 0);
+
+        // Canonicalizing a canonical recording has no effect
+        let canonical = timestamped_input.clone();
+        timestamped_input.canonicalize();
+        assert_eq!(timestamped_input, canonical);
+    }
+
+    #[test]
+    fn renumber_frames() {
+        let mut timestamped_input = TimestampedInputs::default();
+        timestamped_input.send(FrameCount(3), Duration::from_secs(1), LEFT_CLICK_PRESS);
+        timestamped_input.send(FrameCount(3), Duration::from_secs(1), LEFT_CLICK_RELEASE);
+        timestamped_input.send(FrameCount(7), Duration::from_secs(2), LEFT_CLICK_PRESS);
+        timestamped_input.send(FrameCount(20), Duration::from_secs(5), LEFT_CLICK_RELEASE);
+        timestamped_input.send(FrameCount(21), Duration::from_secs(6), LEFT_CLICK_PRESS);
+
+        timestamped_input.renumber_frames();
+
+        let frames: Vec<u32> = timestamped_input
+            .events
+            .iter()
+            .map(|event| event.frame.0)
+            .collect();
+        assert_eq!(frames, vec![0, 0, 1, 2, 3]);
+        // Timestamps are unchanged
+        assert_eq!(
+            timestamped_input.time_range(),
+            Some((Duration::from_secs(1), Duration::from_secs(6)))
+        );
+    }
+
+    #[test]
+    fn count_by_kind() {
+        let mut timestamped_input = complex_timestamped_input();
+        let counts = timestamped_input.count_by_kind();
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts[&InputEventKind::MouseButton], 5);
+
+        timestamped_input.send(FrameCount(4), Duration::from_secs(4), InputEvent::AppExit);
+        let counts = timestamped_input.count_by_kind();
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[&InputEventKind::MouseButton], 5);
+        assert_eq!(counts[&InputEventKind::AppExit], 1);
+        assert_eq!(counts.get(&InputEventKind::Keyboard), None);
+    }
+
+    #[test]
+    fn key_hold_durations() {
+        let key = |key_code: KeyCode, state: ButtonState| {
+            InputEvent::Keyboard(KeyboardInput {
+                key_code,
+                logical_key: bevy::input::keyboard::Key::Space,
+                state,
+                repeat: false,
+                window: Entity::PLACEHOLDER,
+            })
+        };
+
+        let mut timestamped_input = TimestampedInputs::default();
+        timestamped_input.send(
+            FrameCount(0),
+            Duration::from_millis(100),
+            key(KeyCode::Space, ButtonState::Pressed),
+        );
+        // Key repeats do not restart the hold
+        timestamped_input.send(
+            FrameCount(1),
+            Duration::from_millis(200),
+            key(KeyCode::Space, ButtonState::Pressed),
+        );
+        timestamped_input.send(
+            FrameCount(2),
+            Duration::from_millis(350),
+            key(KeyCode::Space, ButtonState::Released),
+        );
+        // Never released, so ignored
+        timestamped_input.send(
+            FrameCount(3),
+            Duration::from_millis(400),
+            key(KeyCode::KeyA, ButtonState::Pressed),
+        );
+
+        let hold_durations = timestamped_input.key_hold_durations();
+        assert_eq!(hold_durations.len(), 1);
+        assert_eq!(
+            hold_durations[&KeyCode::Space],
+            vec![Duration::from_millis(250)]
+        );
+    }
+
+    #[test]
+    fn primary_device() {
+        use bevy::input::gamepad::{GamepadAxis, GamepadAxisChangedEvent};
+
+        assert_eq!(
+            TimestampedInputs::default().primary_device(),
+            InputDevice::Unknown
+        );
+
+        let mut timestamped_input = complex_timestamped_input();
+        for frame in 4..10 {
+            timestamped_input.send(
+                FrameCount(frame),
+                Duration::from_secs(frame as u64),
+                InputEvent::Gamepad(GamepadEvent::Axis(GamepadAxisChangedEvent::new(
+                    Entity::PLACEHOLDER,
+                    GamepadAxis::LeftStickX,
+                    0.5,
+                ))),
+            );
+        }
+        timestamped_input.send(FrameCount(10), Duration::from_secs(10), InputEvent::AppExit);
+        assert_eq!(timestamped_input.primary_device(), InputDevice::Gamepad);
+
+        // Ties favor the device that is declared first
+        let mut tied = TimestampedInputs::default();
+        tied.send(FrameCount(0), Duration::ZERO, LEFT_CLICK_PRESS);
+        tied.send(
+            FrameCount(0),
+            Duration::ZERO,
+            InputEvent::Gamepad(GamepadEvent::Axis(GamepadAxisChangedEvent::new(
+                Entity::PLACEHOLDER,
+                GamepadAxis::LeftStickX,
+                0.5,
+            ))),
+        );
+        assert_eq!(tied.primary_device(), InputDevice::Mouse);
+    }
+
+    #[test]
+    fn gamepad_events() {
+        use bevy::input::gamepad::{
+            GamepadAxis, GamepadAxisChangedEvent, GamepadButtonChangedEvent,
+        };
+
+        let mut world = World::new();
+        let player_one = world.spawn_empty().id();
+        let player_two = world.spawn_empty().id();
+
+        let mut timestamped_input = TimestampedInputs::default();
+        for (i, gamepad) in [player_one, player_two, player_one, player_two]
+            .into_iter()
+            .enumerate()
+        {
+            let frame = FrameCount(i as u32);
+            let button = GamepadEvent::Button(GamepadButtonChangedEvent::new(
+                gamepad,
+                GamepadButton::South,
+                ButtonState::Pressed,
+                1.0,
+            ));
+            let axis = GamepadEvent::Axis(GamepadAxisChangedEvent::new(
+                gamepad,
+                GamepadAxis::LeftStickX,
+                i as f32,
+            ));
+            timestamped_input.send(frame, Duration::ZERO, button.into());
+            timestamped_input.send(frame, Duration::ZERO, LEFT_CLICK_PRESS);
+            timestamped_input.send(frame, Duration::ZERO, axis.into());
+        }
+
+        for gamepad in [player_one, player_two] {
+            let events = timestamped_input.gamepad_events(gamepad);
+            assert_eq!(events.len(), 4);
+            assert!(events
+                .iter()
+                .all(|event| event.input_event.gamepad() == Some(gamepad)));
+        }
+
+        let player_two_frames: Vec<FrameCount> = timestamped_input
+            .gamepad_events(player_two)
+            .into_iter()
+            .map(|event| event.frame)
+            .collect();
+        assert_eq!(
+            player_two_frames,
+            vec![FrameCount(1), FrameCount(1), FrameCount(3), FrameCount(3)]
+        );
+
+        assert!(timestamped_input
+            .gamepad_events(Entity::PLACEHOLDER)
+            .is_empty());
+    }
+
+    #[test]
+    fn downsample() {
+        let mut timestamped_input = TimestampedInputs::default();
+        for i in 0..20 {
+            let frame = FrameCount(i * 5);
+            let button = if i % 2 == 0 {
+                MouseButton::Left
+            } else {
+                MouseButton::Right
+            };
+
+            let cursor_moved = CursorMoved {
+                window: Entity::PLACEHOLDER,
+                position: Vec2::splat(i as f32),
+                delta: None,
+            };
+            let press = MouseButtonInput {
+                button,
+                state: ButtonState::Pressed,
+                window: Entity::PLACEHOLDER,
+            };
+            let release = MouseButtonInput {
+                state: ButtonState::Released,
+                ..press
+            };
+
+            // Three cursor events and a press-release pair every five frames
+            let next_frame = FrameCount(frame.0 + 1);
+            timestamped_input.send(frame, Duration::ZERO, cursor_moved.clone().into());
+            timestamped_input.send(frame, Duration::ZERO, press.into());
+            timestamped_input.send(frame, Duration::ZERO, cursor_moved.clone().into());
+            timestamped_input.send(next_frame, Duration::ZERO, cursor_moved.into());
+            timestamped_input.send(next_frame, Duration::ZERO, release.into());
+        }
+        assert_eq!(timestamped_input.len(), 100);
+
+        timestamped_input.downsample(20);
+        assert_eq!(timestamped_input.len(), 20);
+
+        // Cursor events are dropped first
+        assert!(timestamped_input
+            .events
+            .iter()
+            .all(|event| matches!(event.input_event, InputEvent::MouseButton(_))));
+
+        // Every remaining press is followed by its release
+        let mut held = Vec::new();
+        for event in timestamped_input.events.iter() {
+            let InputEvent::MouseButton(input) = &event.input_event else {
+                unreachable!()
+            };
+            match input.state {
+                ButtonState::Pressed => held.push(input.button),
+                ButtonState::Released => {
+                    assert_eq!(held.pop(), Some(input.button));
+                }
+            }
+        }
+        assert!(held.is_empty());
+    }
+
     #[test]
     fn iter_between_times() {
         let mut timestamped_input = complex_timestamped_input();
@@ -587,4 +2375,226 @@ mod tests {
             timestamped_input.iter_between_times(Duration::from_secs(0), Duration::from_secs(3));
         assert_eq!(iter.into_iter().count(), 3);
     }
+
+    #[test]
+    fn merge_with_prefer_other() {
+        let right_click_press = InputEvent::MouseButton(MouseButtonInput {
+            button: MouseButton::Right,
+            state: ButtonState::Pressed,
+            window: Entity::PLACEHOLDER,
+        });
+        let cursor_moved = InputEvent::CursorMoved(CursorMoved {
+            window: Entity::PLACEHOLDER,
+            position: Vec2::ONE,
+            delta: None,
+        });
+        let mut other = TimestampedInputs::default();
+        other.send(FrameCount(1), Duration::from_secs(1), cursor_moved.clone());
+        other.send(
+            FrameCount(2),
+            Duration::from_secs(2),
+            right_click_press.clone(),
+        );
+        other.send(FrameCount(4), Duration::from_secs(4), LEFT_CLICK_RELEASE);
+
+        let mut timestamped_input = complex_timestamped_input();
+        timestamped_input.next();
+        timestamped_input.merge_with(other, MergePolicy::PreferOther);
+
+        // Both of our mouse button events on frame 2 conflict with the other recording
+        let expected = [
+            (FrameCount(0), LEFT_CLICK_PRESS),
+            (FrameCount(1), LEFT_CLICK_RELEASE),
+            (FrameCount(1), cursor_moved),
+            (FrameCount(2), right_click_press),
+            (FrameCount(3), LEFT_CLICK_PRESS),
+            (FrameCount(4), LEFT_CLICK_RELEASE),
+        ];
+        assert_eq!(timestamped_input.cursor, 0);
+        assert_eq!(timestamped_input.len(), expected.len());
+        for (event, (frame, input_event)) in timestamped_input.events.iter().zip(expected) {
+            assert_eq!(event.frame, frame);
+            assert_eq!(event.input_event, input_event);
+        }
+
+        let mut kept_both = complex_timestamped_input();
+        kept_both.merge(complex_timestamped_input());
+        assert_eq!(kept_both.len(), 10);
+    }
+
+    #[test]
+    fn unbalanced_inputs() {
+        let right_click_release = InputEvent::MouseButton(MouseButtonInput {
+            button: MouseButton::Right,
+            state: ButtonState::Released,
+            window: Entity::PLACEHOLDER,
+        });
+        let key_press = InputEvent::Keyboard(KeyboardInput {
+            key_code: KeyCode::KeyF,
+            logical_key: bevy::input::keyboard::Key::Character("f".into()),
+            state: ButtonState::Pressed,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        });
+
+        let mut timestamped_input = TimestampedInputs::default();
+        timestamped_input.send(FrameCount(0), Duration::from_secs(0), key_press.clone());
+        timestamped_input.send(FrameCount(1), Duration::from_secs(1), LEFT_CLICK_PRESS);
+        timestamped_input.send(
+            FrameCount(2),
+            Duration::from_secs(2),
+            right_click_release.clone(),
+        );
+        timestamped_input.send(FrameCount(3), Duration::from_secs(3), LEFT_CLICK_RELEASE);
+        // A repeated press is still only reported once
+        timestamped_input.send(FrameCount(4), Duration::from_secs(4), key_press.clone());
+
+        assert_eq!(
+            timestamped_input.unbalanced_inputs(),
+            vec![key_press, right_click_release]
+        );
+        assert_eq!(
+            complex_timestamped_input().unbalanced_inputs(),
+            vec![LEFT_CLICK_PRESS]
+        );
+    }
+
+    #[test]
+    fn trajectories_ignore_order_within_frame() {
+        let key_press = InputEvent::Keyboard(KeyboardInput {
+            key_code: KeyCode::KeyF,
+            logical_key: bevy::input::keyboard::Key::Character("f".into()),
+            state: ButtonState::Pressed,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        });
+        let cursor_moved = InputEvent::CursorMoved(CursorMoved {
+            window: Entity::PLACEHOLDER,
+            position: Vec2::ONE,
+            delta: None,
+        });
+
+        let mut a = TimestampedInputs::default();
+        a.send(FrameCount(0), Duration::ZERO, LEFT_CLICK_PRESS);
+        a.send(FrameCount(0), Duration::ZERO, key_press.clone());
+        a.send(FrameCount(2), Duration::from_secs(2), LEFT_CLICK_RELEASE);
+
+        // The same presses in a different order within the frame, with an unrelated event
+        let mut b = TimestampedInputs::default();
+        b.send(FrameCount(0), Duration::ZERO, key_press.clone());
+        b.send(FrameCount(0), Duration::ZERO, LEFT_CLICK_PRESS);
+        b.send(FrameCount(1), Duration::from_secs(1), cursor_moved);
+        b.send(FrameCount(2), Duration::from_secs(2), LEFT_CLICK_RELEASE);
+        assert!(equivalent_trajectories(&a, &b));
+
+        // The release happens a frame later
+        let mut c = TimestampedInputs::default();
+        c.send(FrameCount(0), Duration::ZERO, LEFT_CLICK_PRESS);
+        c.send(FrameCount(0), Duration::ZERO, key_press);
+        c.send(FrameCount(3), Duration::from_secs(3), LEFT_CLICK_RELEASE);
+        assert!(!equivalent_trajectories(&a, &c));
+    }
+
+    #[test]
+    fn balance_inputs() {
+        let gamepad_press = InputEvent::Gamepad(GamepadEvent::Button(GamepadButtonChangedEvent {
+            entity: Entity::PLACEHOLDER,
+            button: GamepadButton::South,
+            state: ButtonState::Pressed,
+            value: 1.0,
+        }));
+        let key_press = InputEvent::Keyboard(KeyboardInput {
+            key_code: KeyCode::KeyF,
+            logical_key: bevy::input::keyboard::Key::Character("f".into()),
+            state: ButtonState::Pressed,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        });
+
+        let mut timestamped_input = complex_timestamped_input();
+        timestamped_input.send(FrameCount(4), Duration::from_secs(4), gamepad_press);
+        timestamped_input.send(FrameCount(5), Duration::from_secs(5), key_press);
+        timestamped_input.balance_inputs();
+
+        assert!(timestamped_input.unbalanced_inputs().is_empty());
+        let appended = &timestamped_input.events[7..];
+        assert_eq!(appended.len(), 3);
+        for event in appended {
+            assert_eq!(event.frame, FrameCount(5));
+            assert_eq!(event.time_since_startup, Duration::from_secs(5));
+            let (_, state) = event.input_event.buttonlike_state().unwrap();
+            assert_eq!(state, ButtonState::Released);
+        }
+        assert_eq!(appended[0].input_event, LEFT_CLICK_RELEASE);
+    }
+
+    #[test]
+    fn recording_format_compatibility() {
+        assert!(is_compatible(RECORDING_FORMAT_VERSION));
+        // Older recordings, including those made before versioning, can still be read
+        assert!(is_compatible(RECORDING_FORMAT_VERSION - 1));
+        assert!(is_compatible(0));
+        assert!(!is_compatible(RECORDING_FORMAT_VERSION + 1));
+    }
+
+    #[test]
+    fn with_capacity() {
+        let timestamped_input = TimestampedInputs::with_capacity(100);
+
+        assert!(timestamped_input.is_empty());
+        assert_eq!(timestamped_input.cursor, 0);
+        assert!(timestamped_input.events.capacity() >= 100);
+    }
+
+    #[test]
+    fn input_rates() {
+        use bevy::input::keyboard::Key;
+
+        let press = |button| {
+            InputEvent::MouseButton(MouseButtonInput {
+                button,
+                state: ButtonState::Pressed,
+                window: Entity::PLACEHOLDER,
+            })
+        };
+        let key = |state, repeat| {
+            InputEvent::Keyboard(KeyboardInput {
+                logical_key: Key::Space,
+                key_code: KeyCode::Space,
+                state,
+                repeat,
+                window: Entity::PLACEHOLDER,
+            })
+        };
+
+        let mut timestamped_input = TimestampedInputs::default();
+        assert_eq!(timestamped_input.actions_per_minute(), 0.0);
+        assert_eq!(timestamped_input.clicks_per_second(), 0.0);
+
+        // Four clicks and two key presses over two seconds
+        for (seconds, input_event) in [
+            (0, press(MouseButton::Left)),
+            (0, key(ButtonState::Pressed, false)),
+            (1, key(ButtonState::Pressed, true)),
+            (1, press(MouseButton::Right)),
+            (1, key(ButtonState::Released, false)),
+            (2, press(MouseButton::Left)),
+            (2, press(MouseButton::Left)),
+            (2, key(ButtonState::Pressed, false)),
+        ] {
+            timestamped_input.send(
+                FrameCount(seconds),
+                Duration::from_secs(seconds as u64),
+                input_event,
+            );
+        }
+
+        assert_eq!(timestamped_input.clicks_per_second(), 2.0);
+        assert_eq!(timestamped_input.actions_per_minute(), 180.0);
+
+        // A recording without any duration has no meaningful rate
+        timestamped_input.events.truncate(2);
+        assert_eq!(timestamped_input.actions_per_minute(), 0.0);
+        assert_eq!(timestamped_input.clicks_per_second(), 0.0);
+    }
 }
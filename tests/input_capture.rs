@@ -5,11 +5,14 @@ use bevy::input::mouse::MouseButtonInput;
 use bevy::input::ButtonState;
 use bevy::input::InputPlugin;
 use bevy::prelude::*;
-use bevy::window::WindowPlugin;
+use bevy::utils::Duration;
+use bevy::window::{PrimaryWindow, WindowPlugin};
 
 use leafwing_input_playback::input_capture::EndInputCapture;
 use leafwing_input_playback::input_capture::{
-    BeginInputCapture, InputCapturePlugin, InputModesCaptured,
+    ActiveNamedCaptures, BeginInputCapture, BeginNamedInputCapture, CaptureFilter, CaptureState,
+    EndNamedInputCapture, InputCapturePlugin, InputModesCaptured, NamedRecordings,
+    PauseInputCapture, ResumeInputCapture,
 };
 use leafwing_input_playback::timestamped_input::{
     InputEvent, TimestampedInputEvent, TimestampedInputs,
@@ -44,7 +47,7 @@ fn capture_app() -> App {
         MinimalPlugins,
         WindowPlugin::default(),
         InputPlugin,
-        InputCapturePlugin,
+        InputCapturePlugin::default(),
     ));
     app
 }
@@ -211,3 +214,895 @@ fn end_input_capture() {
     let timestamped_input = app.world().resource::<TimestampedInputs>();
     assert_eq!(timestamped_input.len(), 2);
 }
+
+#[test]
+fn first_capture_limit_wins() {
+    use bevy::time::TimeUpdateStrategy;
+    use leafwing_input_playback::input_capture::CaptureStopCondition;
+
+    // Returns the number of updates for which capture was active
+    let updates_captured = |begin_input_capture: BeginInputCapture| {
+        let mut app = capture_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+        // The first update has no elapsed time
+        app.update();
+        app.world_mut().trigger(begin_input_capture);
+        let mut updates = 0;
+        while app.world().resource::<CaptureState>().is_capturing() && updates < 100 {
+            app.update();
+            updates += 1;
+        }
+        updates
+    };
+
+    let frames_first = BeginInputCapture {
+        frames_to_capture: Some(FrameCount(3)),
+        duration_to_capture: Some(Duration::from_secs(1)),
+        ..Default::default()
+    };
+    assert_eq!(
+        frames_first.stop_condition(),
+        CaptureStopCondition::FirstOf(FrameCount(3), Duration::from_secs(1))
+    );
+    let frames_only = BeginInputCapture {
+        frames_to_capture: Some(FrameCount(3)),
+        ..Default::default()
+    };
+    assert_eq!(
+        updates_captured(frames_first),
+        updates_captured(frames_only)
+    );
+
+    let duration_first = BeginInputCapture {
+        frames_to_capture: Some(FrameCount(10)),
+        duration_to_capture: Some(Duration::from_millis(500)),
+        ..Default::default()
+    };
+    assert_eq!(updates_captured(duration_first), 5);
+
+    assert_eq!(
+        BeginInputCapture::default().stop_condition(),
+        CaptureStopCondition::Manual
+    );
+}
+
+#[test]
+fn preserve_existing_inputs() {
+    let mut app = capture_app();
+
+    let mut existing_inputs = TimestampedInputs::default();
+    existing_inputs.send(FrameCount(0), Duration::ZERO, TEST_PRESS.into());
+    existing_inputs.send(FrameCount(1), Duration::ZERO, TEST_RELEASE.into());
+    app.insert_resource(existing_inputs);
+
+    app.world_mut().trigger(BeginInputCapture {
+        preserve_existing: true,
+        ..Default::default()
+    });
+
+    let mut mouse_events = app.world_mut().resource_mut::<Events<MouseButtonInput>>();
+    mouse_events.send(TEST_MOUSE);
+
+    app.update();
+
+    // The loaded events remain, and new events are appended after them
+    let timestamped_input = app.world().resource::<TimestampedInputs>();
+    assert_eq!(timestamped_input.len(), 3);
+    assert!(matches!(
+        timestamped_input.events[0].input_event,
+        InputEvent::Keyboard(_)
+    ));
+    assert!(matches!(
+        timestamped_input.events[2].input_event,
+        InputEvent::MouseButton(_)
+    ));
+
+    // Without preservation, the existing events are discarded
+    app.world_mut().trigger(BeginInputCapture {
+        preserve_existing: false,
+        ..Default::default()
+    });
+    app.update();
+
+    let timestamped_input = app.world().resource::<TimestampedInputs>();
+    assert!(timestamped_input.is_empty());
+}
+
+#[test]
+fn capture_edges_only() {
+    let mut app = capture_app();
+    app.world_mut().trigger(BeginInputCapture {
+        edge_only: true,
+        ..Default::default()
+    });
+
+    for event in [TEST_PRESS, TEST_PRESS, TEST_RELEASE, TEST_RELEASE] {
+        let mut keyboard_events = app.world_mut().resource_mut::<Events<KeyboardInput>>();
+        keyboard_events.send(event);
+        app.update();
+    }
+
+    // Only the changes in state were captured
+    let timestamped_input = app.world().resource::<TimestampedInputs>();
+    assert_eq!(timestamped_input.len(), 2);
+    assert_eq!(
+        timestamped_input.events[0].input_event,
+        InputEvent::Keyboard(TEST_PRESS)
+    );
+    assert_eq!(
+        timestamped_input.events[1].input_event,
+        InputEvent::Keyboard(TEST_RELEASE)
+    );
+}
+
+#[test]
+fn capture_state_transitions() {
+    let mut app = capture_app();
+    assert_eq!(*app.world().resource::<CaptureState>(), CaptureState::Idle);
+
+    app.world_mut().trigger(BeginInputCapture::default());
+    assert_eq!(
+        *app.world().resource::<CaptureState>(),
+        CaptureState::Capturing
+    );
+
+    let mut keyboard_events = app.world_mut().resource_mut::<Events<KeyboardInput>>();
+    keyboard_events.send(TEST_PRESS);
+    app.update();
+    assert_eq!(app.world().resource::<TimestampedInputs>().len(), 1);
+
+    // Inputs are not captured while paused
+    app.world_mut().trigger(PauseInputCapture);
+    assert_eq!(
+        *app.world().resource::<CaptureState>(),
+        CaptureState::Paused
+    );
+
+    let mut keyboard_events = app.world_mut().resource_mut::<Events<KeyboardInput>>();
+    keyboard_events.send(TEST_RELEASE);
+    app.update();
+    assert_eq!(app.world().resource::<TimestampedInputs>().len(), 1);
+
+    app.world_mut().trigger(ResumeInputCapture);
+    assert_eq!(
+        *app.world().resource::<CaptureState>(),
+        CaptureState::Capturing
+    );
+
+    let mut keyboard_events = app.world_mut().resource_mut::<Events<KeyboardInput>>();
+    keyboard_events.send(TEST_RELEASE);
+    app.update();
+    assert_eq!(app.world().resource::<TimestampedInputs>().len(), 2);
+
+    app.world_mut().trigger(EndInputCapture);
+    assert_eq!(*app.world().resource::<CaptureState>(), CaptureState::Idle);
+
+    // Resuming has no effect once capture has ended
+    app.world_mut().trigger(ResumeInputCapture);
+    assert_eq!(*app.world().resource::<CaptureState>(), CaptureState::Idle);
+}
+
+#[test]
+fn snapshot_cursor_position_on_begin() {
+    let mut app = capture_app();
+    let cursor_position = Vec2::new(10.0, 20.0);
+
+    let mut primary_window = app
+        .world_mut()
+        .query_filtered::<&mut Window, With<PrimaryWindow>>();
+    primary_window
+        .single_mut(app.world_mut())
+        .set_cursor_position(Some(cursor_position));
+
+    app.world_mut().trigger(BeginInputCapture::default());
+    app.update();
+
+    let timestamped_input = app.world().resource::<TimestampedInputs>();
+    assert_eq!(timestamped_input.len(), 1);
+
+    let first_event = &timestamped_input.events[0];
+    assert_eq!(first_event.frame, FrameCount(0));
+    let InputEvent::CursorMoved(cursor_moved) = &first_event.input_event else {
+        panic!(
+            "Expected a cursor event, found {:?}",
+            first_event.input_event
+        );
+    };
+    assert_eq!(cursor_moved.position, cursor_position);
+
+    // The cursor is not snapshotted if mouse motion is not captured
+    app.world_mut().trigger(BeginInputCapture {
+        input_modes_captured: InputModesCaptured {
+            mouse_motion: false,
+            ..Default::default()
+        },
+        preserve_existing: false,
+        ..Default::default()
+    });
+    app.update();
+
+    let timestamped_input = app.world().resource::<TimestampedInputs>();
+    assert!(timestamped_input.is_empty());
+
+    // The snapshot respects the capture filter
+    app.insert_resource(CaptureFilter::new(|input_event| {
+        !matches!(input_event, InputEvent::CursorMoved(_))
+    }));
+    app.world_mut().trigger(BeginInputCapture {
+        preserve_existing: false,
+        ..Default::default()
+    });
+    app.update();
+
+    let timestamped_input = app.world().resource::<TimestampedInputs>();
+    assert!(timestamped_input.is_empty());
+}
+
+#[test]
+fn record_window_resolution() {
+    let mut app = capture_app();
+    let primary_window = app
+        .world_mut()
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .single(app.world());
+    app.world_mut()
+        .entity_mut(primary_window)
+        .get_mut::<Window>()
+        .unwrap()
+        .resolution
+        .set(800., 600.);
+
+    app.world_mut().trigger(BeginInputCapture::default());
+    app.update();
+
+    let timestamped_input = app.world().resource::<TimestampedInputs>();
+    assert_eq!(
+        timestamped_input.header.recorded_resolution,
+        Some(Vec2::new(800., 600.))
+    );
+}
+
+#[test]
+fn concurrent_named_captures() {
+    let mut app = capture_app();
+    app.world_mut().trigger(BeginNamedInputCapture {
+        name: "everything".to_string(),
+        input_modes_captured: InputModesCaptured::ENABLE_ALL,
+    });
+    app.world_mut().trigger(BeginNamedInputCapture {
+        name: "keyboard".to_string(),
+        input_modes_captured: InputModesCaptured {
+            keyboard: true,
+            ..InputModesCaptured::DISABLE_ALL
+        },
+    });
+
+    let mut keyboard_events = app.world_mut().resource_mut::<Events<KeyboardInput>>();
+    keyboard_events.send(TEST_PRESS);
+    let mut mouse_events = app.world_mut().resource_mut::<Events<MouseButtonInput>>();
+    mouse_events.send(TEST_MOUSE);
+    app.update();
+
+    app.world_mut().trigger(EndNamedInputCapture {
+        name: "everything".to_string(),
+    });
+    let active_captures = app.world().resource::<ActiveNamedCaptures>();
+    assert!(!active_captures.is_active("everything"));
+    assert!(active_captures.is_active("keyboard"));
+
+    let mut keyboard_events = app.world_mut().resource_mut::<Events<KeyboardInput>>();
+    keyboard_events.send(TEST_RELEASE);
+    app.update();
+
+    let named_recordings = app.world().resource::<NamedRecordings>();
+    let everything = &named_recordings.0["everything"];
+    assert_eq!(everything.len(), 2);
+    assert!(everything
+        .events
+        .iter()
+        .any(|event| event.input_event == InputEvent::Keyboard(TEST_PRESS)));
+    assert!(everything
+        .events
+        .iter()
+        .any(|event| event.input_event == InputEvent::MouseButton(TEST_MOUSE)));
+
+    let keyboard = &named_recordings.0["keyboard"];
+    assert_eq!(keyboard.len(), 2);
+    assert_eq!(
+        keyboard.events[0].input_event,
+        InputEvent::Keyboard(TEST_PRESS)
+    );
+    assert_eq!(
+        keyboard.events[1].input_event,
+        InputEvent::Keyboard(TEST_RELEASE)
+    );
+
+    // The global capture lifecycle is unaffected
+    assert!(!app.world().contains_resource::<TimestampedInputs>());
+}
+
+#[test]
+fn named_captures_match_global_capture() {
+    let mut app = capture_app();
+    app.insert_resource(CaptureFilter::new(|input_event| {
+        !matches!(input_event, InputEvent::MouseButton(_))
+    }));
+    app.world_mut().trigger(BeginInputCapture::default());
+    app.world_mut().trigger(BeginNamedInputCapture {
+        name: "named".to_string(),
+        input_modes_captured: InputModesCaptured::ENABLE_ALL,
+    });
+
+    let mut keyboard_events = app.world_mut().resource_mut::<Events<KeyboardInput>>();
+    keyboard_events.send(TEST_PRESS);
+    let mut mouse_events = app.world_mut().resource_mut::<Events<MouseButtonInput>>();
+    mouse_events.send(TEST_MOUSE);
+    app.update();
+
+    // Nothing is captured into either recording while capture is paused
+    app.world_mut().trigger(PauseInputCapture);
+    let mut keyboard_events = app.world_mut().resource_mut::<Events<KeyboardInput>>();
+    keyboard_events.send(TEST_RELEASE);
+    app.update();
+
+    let timestamped_input = app.world().resource::<TimestampedInputs>();
+    let named_recordings = app.world().resource::<NamedRecordings>();
+    let named = &named_recordings.0["named"];
+    assert_eq!(named.len(), 1);
+    assert_eq!(named.events, timestamped_input.events);
+    assert_eq!(
+        named.events[0].input_event,
+        InputEvent::Keyboard(TEST_PRESS)
+    );
+}
+
+#[test]
+fn idle_markers_preserve_idle_periods() {
+    let mut app = capture_app();
+    app.world_mut().trigger(BeginInputCapture {
+        idle_marker_interval: Some(10),
+        ..Default::default()
+    });
+
+    let mut keyboard_events = app.world_mut().resource_mut::<Events<KeyboardInput>>();
+    keyboard_events.send(TEST_PRESS);
+    app.update();
+
+    // A long idle period
+    for _ in 0..35 {
+        app.update();
+    }
+
+    let mut keyboard_events = app.world_mut().resource_mut::<Events<KeyboardInput>>();
+    keyboard_events.send(TEST_RELEASE);
+    app.update();
+
+    let timestamped_input = app.world().resource::<TimestampedInputs>();
+    let marker_frames: Vec<FrameCount> = timestamped_input
+        .events
+        .iter()
+        .filter(|event| event.input_event == InputEvent::IdleMarker)
+        .map(|event| event.frame)
+        .collect();
+    assert_eq!(
+        marker_frames,
+        vec![FrameCount(10), FrameCount(20), FrameCount(30)]
+    );
+    assert_eq!(timestamped_input.len(), 5);
+}
+
+#[test]
+fn auto_begin_capture_on_startup() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        WindowPlugin::default(),
+        InputPlugin,
+        InputCapturePlugin {
+            auto_begin: Some(BeginInputCapture::default()),
+        },
+    ));
+
+    let mut keyboard_events = app.world_mut().resource_mut::<Events<KeyboardInput>>();
+    keyboard_events.send(TEST_PRESS);
+    app.update();
+
+    assert_eq!(
+        *app.world().resource::<CaptureState>(),
+        CaptureState::Capturing
+    );
+    let timestamped_input = app.world().resource::<TimestampedInputs>();
+    assert_eq!(timestamped_input.len(), 1);
+}
+
+#[test]
+fn capture_filter() {
+    let mut app = capture_app();
+    app.insert_resource(CaptureFilter::new(|input_event| match input_event {
+        InputEvent::Keyboard(event) => event.state == ButtonState::Pressed,
+        InputEvent::MouseButton(event) => event.state == ButtonState::Pressed,
+        _ => true,
+    }));
+    app.world_mut().trigger(BeginInputCapture::default());
+
+    let mut keyboard_events = app.world_mut().resource_mut::<Events<KeyboardInput>>();
+    keyboard_events.send(TEST_PRESS);
+    keyboard_events.send(TEST_RELEASE);
+    let mut mouse_events = app.world_mut().resource_mut::<Events<MouseButtonInput>>();
+    mouse_events.send(TEST_MOUSE);
+    mouse_events.send(MouseButtonInput {
+        state: ButtonState::Released,
+        ..TEST_MOUSE
+    });
+    app.update();
+
+    let timestamped_input = app.world().resource::<TimestampedInputs>();
+    assert_eq!(timestamped_input.len(), 2);
+    assert!(timestamped_input.events.iter().all(|event| matches!(
+        &event.input_event,
+        InputEvent::Keyboard(KeyboardInput {
+            state: ButtonState::Pressed,
+            ..
+        }) | InputEvent::MouseButton(MouseButtonInput {
+            state: ButtonState::Pressed,
+            ..
+        })
+    )));
+}
+
+#[test]
+fn rumble_quantization() {
+    use bevy::input::gamepad::{GamepadRumbleIntensity, GamepadRumbleRequest};
+    use leafwing_input_playback::timestamped_input::RumbleRequest;
+
+    let mut app = capture_app();
+    app.world_mut().trigger(BeginInputCapture {
+        rumble_quantization: Some(0.1),
+        ..Default::default()
+    });
+    let gamepad = app.world_mut().spawn_empty().id();
+
+    // A slow ramp, with many small updates per quantization step
+    for step in 0..=50 {
+        app.world_mut().send_event(GamepadRumbleRequest::Add {
+            duration: Duration::from_millis(100),
+            intensity: GamepadRumbleIntensity::strong_motor(step as f32 / 100.),
+            gamepad,
+        });
+        app.update();
+    }
+
+    // The same intensity for a different duration is a new request
+    app.world_mut().send_event(GamepadRumbleRequest::Add {
+        duration: Duration::from_millis(200),
+        intensity: GamepadRumbleIntensity::strong_motor(0.5),
+        gamepad,
+    });
+    app.update();
+
+    let timestamped_input = app.world().resource::<TimestampedInputs>();
+    let strong_motor_intensities: Vec<f32> = timestamped_input
+        .events
+        .iter()
+        .map(|event| match event.input_event {
+            InputEvent::GamepadRumble(RumbleRequest::Add { strong_motor, .. }) => strong_motor,
+            _ => panic!("Only rumble requests were sent"),
+        })
+        .collect();
+    assert_eq!(strong_motor_intensities.len(), 7);
+    for (captured, expected) in strong_motor_intensities
+        .iter()
+        .zip([0., 0.1, 0.2, 0.3, 0.4, 0.5, 0.5])
+    {
+        assert!((captured - expected).abs() < f32::EPSILON);
+    }
+}
+
+#[cfg(feature = "file")]
+#[test]
+fn flush_streaming_capture() {
+    use leafwing_input_playback::input_capture::FlushCapture;
+    use leafwing_input_playback::serde::read_streamed_recording;
+
+    let path = std::env::temp_dir().join("leafwing_input_playback_flush_capture.ron");
+    let mut app = capture_app();
+    app.world_mut().trigger(BeginInputCapture {
+        filepath: Some(path.clone()),
+        // Large enough that events are only written when flushed
+        stream_buffer_size: Some(100),
+        ..Default::default()
+    });
+
+    app.world_mut().send_event(TEST_PRESS);
+    app.update();
+    app.world_mut().trigger(FlushCapture);
+    let partial = read_streamed_recording(&path).unwrap();
+    assert_eq!(partial.len(), 1);
+    assert_eq!(
+        partial.events[0].input_event,
+        InputEvent::Keyboard(TEST_PRESS)
+    );
+
+    app.world_mut().send_event(TEST_RELEASE);
+    app.update();
+    // Nothing new is written until the next flush
+    assert_eq!(read_streamed_recording(&path).unwrap().len(), 1);
+    assert_eq!(
+        *app.world().resource::<CaptureState>(),
+        CaptureState::Capturing
+    );
+
+    app.world_mut().trigger(FlushCapture);
+    let partial = read_streamed_recording(&path).unwrap();
+    assert_eq!(partial.len(), 2);
+    assert_eq!(
+        partial.events[1].input_event,
+        InputEvent::Keyboard(TEST_RELEASE)
+    );
+
+    app.world_mut().trigger(EndInputCapture);
+    app.world_mut().flush();
+    assert_eq!(read_streamed_recording(&path).unwrap().len(), 2);
+}
+
+#[cfg(feature = "file")]
+#[test]
+fn filepaths_accept_path_like_values() {
+    use leafwing_input_playback::serde::PlaybackFilePath;
+    use std::path::PathBuf;
+
+    let expected = Some(PathBuf::from("recording.ron"));
+    let begins = [
+        BeginInputCapture::default().with_file("recording.ron"),
+        BeginInputCapture::default().with_file(String::from("recording.ron")),
+        BeginInputCapture::default().with_file(PathBuf::from("recording.ron")),
+    ];
+    for begin_input_capture in begins {
+        assert_eq!(begin_input_capture.filepath, expected);
+    }
+
+    assert_eq!(PlaybackFilePath::new("recording.ron").path(), &expected);
+    assert_eq!(
+        PlaybackFilePath::new(String::from("recording.ron")).path(),
+        &expected
+    );
+    assert_eq!(
+        PlaybackFilePath::new(PathBuf::from("recording.ron")).path(),
+        &expected
+    );
+}
+
+#[cfg(feature = "file")]
+#[test]
+fn recording_metadata_round_trip() {
+    use bevy::utils::HashMap;
+    use leafwing_input_playback::input_capture::RecordingMetadata;
+    use leafwing_input_playback::serde::read_recording;
+
+    let path = std::env::temp_dir().join("leafwing_input_playback_metadata.ron");
+    let metadata: HashMap<String, String> = [
+        ("build".to_string(), "1a2b3c".to_string()),
+        (
+            "test".to_string(),
+            "recording_metadata_round_trip".to_string(),
+        ),
+    ]
+    .into_iter()
+    .collect();
+
+    let mut app = capture_app();
+    app.insert_resource(RecordingMetadata(metadata.clone()));
+    app.world_mut().trigger(BeginInputCapture {
+        filepath: Some(path.clone()),
+        ..Default::default()
+    });
+    app.world_mut().send_event(TEST_PRESS);
+    app.update();
+    app.world_mut().trigger(EndInputCapture);
+    app.world_mut().flush();
+
+    let loaded = read_recording(std::fs::File::open(&path).unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.header.metadata, metadata);
+    assert_eq!(loaded.len(), 1);
+}
+
+#[cfg(feature = "file")]
+#[test]
+fn canonicalized_captures_are_identical() {
+    let capture = |name: &str, events: &[InputEvent]| {
+        let path = std::env::temp_dir().join(name);
+        let mut app = capture_app();
+        app.world_mut().trigger(BeginInputCapture {
+            filepath: Some(path.clone()),
+            canonicalize: true,
+            ..Default::default()
+        });
+        for event in events {
+            match event {
+                InputEvent::Keyboard(event) => {
+                    app.world_mut().send_event(event.clone());
+                }
+                InputEvent::MouseButton(event) => {
+                    app.world_mut().send_event(*event);
+                }
+                _ => unreachable!(),
+            }
+        }
+        app.update();
+        app.world_mut().trigger(EndInputCapture);
+        app.world_mut().flush();
+
+        let contents = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        contents
+    };
+
+    let press = InputEvent::Keyboard(TEST_PRESS);
+    let click = InputEvent::MouseButton(TEST_MOUSE);
+    let first = capture(
+        "leafwing_input_playback_canonical_a.ron",
+        &[press.clone(), click.clone()],
+    );
+    // Same inputs, in a different order within the frame and with a duplicate
+    let second = capture(
+        "leafwing_input_playback_canonical_b.ron",
+        &[click.clone(), press, click],
+    );
+    assert_eq!(first, second);
+}
+
+#[cfg(feature = "file")]
+#[test]
+fn end_on_focus_loss() {
+    use bevy::window::WindowFocused;
+    use leafwing_input_playback::serde::read_recording;
+
+    let path = std::env::temp_dir().join("leafwing_input_playback_end_on_focus_loss.ron");
+    let mut app = capture_app();
+    let primary_window = app
+        .world_mut()
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .single(app.world());
+    let other_window = app.world_mut().spawn(Window::default()).id();
+
+    app.world_mut().trigger(BeginInputCapture {
+        filepath: Some(path.clone()),
+        end_on_focus_loss: true,
+        ..Default::default()
+    });
+    app.world_mut().send_event(TEST_PRESS);
+    // Other windows losing focus does not end capture
+    app.world_mut().send_event(WindowFocused {
+        window: other_window,
+        focused: false,
+    });
+    app.update();
+    assert_eq!(
+        *app.world().resource::<CaptureState>(),
+        CaptureState::Capturing
+    );
+
+    app.world_mut().send_event(WindowFocused {
+        window: primary_window,
+        focused: false,
+    });
+    app.update();
+    assert_eq!(*app.world().resource::<CaptureState>(), CaptureState::Idle);
+
+    let loaded = read_recording(std::fs::File::open(&path).unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.len(), 1);
+}
+
+#[cfg(feature = "states")]
+#[test]
+fn capture_in_state() {
+    use bevy::state::app::StatesPlugin;
+    use leafwing_input_playback::input_capture::CaptureInStatePlugin;
+
+    #[derive(States, Debug, Clone, PartialEq, Eq, Hash, Default)]
+    enum GameState {
+        #[default]
+        Menu,
+        InGame,
+    }
+
+    let mut app = capture_app();
+    app.add_plugins((StatesPlugin, CaptureInStatePlugin::new(GameState::InGame)))
+        .init_state::<GameState>();
+    app.update();
+
+    // Capture begins in the menu, so it starts out paused
+    app.world_mut().trigger(BeginInputCapture::default());
+    app.world_mut().flush();
+    assert_eq!(
+        *app.world().resource::<CaptureState>(),
+        CaptureState::Paused
+    );
+    app.world_mut().send_event(TEST_PRESS);
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::InGame);
+    app.world_mut().send_event(TEST_MOUSE);
+    app.update();
+    assert_eq!(
+        *app.world().resource::<CaptureState>(),
+        CaptureState::Capturing
+    );
+
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Menu);
+    app.world_mut().send_event(TEST_RELEASE);
+    app.update();
+    assert_eq!(
+        *app.world().resource::<CaptureState>(),
+        CaptureState::Paused
+    );
+
+    let timestamped_input = app.world().resource::<TimestampedInputs>();
+    let captured: Vec<InputEvent> = timestamped_input
+        .events
+        .iter()
+        .map(|event| event.input_event.clone())
+        .collect();
+    assert_eq!(captured, vec![InputEvent::MouseButton(TEST_MOUSE)]);
+}
+
+#[test]
+fn capture_modifiers() {
+    use leafwing_input_playback::timestamped_input::Modifiers;
+
+    let mut app = capture_app();
+    app.world_mut().trigger(BeginInputCapture {
+        capture_modifiers: true,
+        ..Default::default()
+    });
+
+    app.world_mut().send_event(KeyboardInput {
+        logical_key: Key::Control,
+        key_code: KeyCode::ControlLeft,
+        state: ButtonState::Pressed,
+        window: Entity::PLACEHOLDER,
+        repeat: false,
+    });
+    app.update();
+    app.world_mut().send_event(TEST_MOUSE);
+    app.update();
+
+    let timestamped_input = app.world().resource::<TimestampedInputs>();
+    let click = timestamped_input
+        .events
+        .iter()
+        .find(|event| event.input_event == InputEvent::MouseButton(TEST_MOUSE))
+        .unwrap();
+    assert_eq!(
+        click.modifiers,
+        Some(Modifiers {
+            ctrl: true,
+            ..Default::default()
+        })
+    );
+}
+
+#[test]
+fn precise_timestamps() {
+    let mut app = capture_app();
+    app.world_mut().trigger(BeginInputCapture {
+        precise_timestamps: true,
+        ..Default::default()
+    });
+
+    let mut keyboard_events = app.world_mut().resource_mut::<Events<KeyboardInput>>();
+    keyboard_events.send(TEST_PRESS);
+    keyboard_events.send(TEST_RELEASE);
+    app.update();
+
+    let timestamped_input = app.world().resource::<TimestampedInputs>();
+    let [press, release] = &timestamped_input.events[..] else {
+        panic!("expected two events, found {:?}", timestamped_input.events);
+    };
+    // Both events share a frame timestamp, and precise timestamps never decrease
+    assert_eq!(press.time_since_startup, release.time_since_startup);
+    let (Some(press_time), Some(release_time)) = (press.precise_time, release.precise_time) else {
+        panic!("events were not stamped with precise timestamps");
+    };
+    assert!(press_time <= release_time);
+}
+
+#[test]
+fn no_precise_timestamps_by_default() {
+    let mut app = capture_app();
+    app.world_mut().trigger(BeginInputCapture::default());
+
+    let mut keyboard_events = app.world_mut().resource_mut::<Events<KeyboardInput>>();
+    keyboard_events.send(TEST_PRESS);
+    app.update();
+
+    let timestamped_input = app.world().resource::<TimestampedInputs>();
+    assert_eq!(timestamped_input.len(), 1);
+    assert_eq!(timestamped_input.events[0].precise_time, None);
+}
+
+#[test]
+fn precise_timestamps_for_accumulated_mouse() {
+    use bevy::input::mouse::MouseMotion;
+    use bevy::math::Vec2;
+
+    let mut app = capture_app();
+    app.world_mut().trigger(BeginInputCapture {
+        capture_accumulated_mouse: true,
+        precise_timestamps: true,
+        ..Default::default()
+    });
+
+    app.world_mut().send_event(MouseMotion {
+        delta: Vec2::new(1., 2.),
+    });
+    app.world_mut()
+        .resource_mut::<Events<KeyboardInput>>()
+        .send(TEST_PRESS);
+    app.update();
+
+    let timestamped_input = app.world().resource::<TimestampedInputs>();
+    assert_eq!(timestamped_input.len(), 2);
+    assert!(timestamped_input
+        .events
+        .iter()
+        .all(|event| event.precise_time.is_some()));
+}
+
+#[test]
+fn capture_while_unfocused() {
+    // Returns the captured keyboard states after pressing while unfocused, then releasing while focused
+    let captured_states = |capture_while_unfocused: bool| {
+        let mut app = capture_app();
+        let window = app
+            .world_mut()
+            .query_filtered::<Entity, With<PrimaryWindow>>()
+            .single(app.world());
+        app.world_mut().trigger(BeginInputCapture {
+            capture_while_unfocused,
+            ..Default::default()
+        });
+
+        let set_focus = |app: &mut App, focused: bool| {
+            app.world_mut().get_mut::<Window>(window).unwrap().focused = focused;
+        };
+        set_focus(&mut app, false);
+        app.world_mut().send_event(KeyboardInput {
+            window,
+            ..TEST_PRESS
+        });
+        app.update();
+
+        set_focus(&mut app, true);
+        app.world_mut().send_event(KeyboardInput {
+            window,
+            ..TEST_RELEASE
+        });
+        app.update();
+
+        app.world()
+            .resource::<TimestampedInputs>()
+            .events
+            .iter()
+            .map(|event| match &event.input_event {
+                InputEvent::Keyboard(event) => event.state,
+                other => panic!("expected a keyboard event, found {other:?}"),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        captured_states(true),
+        vec![ButtonState::Pressed, ButtonState::Released]
+    );
+    // The press is dropped, as the window did not have focus
+    assert_eq!(captured_states(false), vec![ButtonState::Released]);
+}
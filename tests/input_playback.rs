@@ -1,8 +1,7 @@
-// BLOCKED: add time strategy tests: https://github.com/bevyengine/bevy/issues/6146
-
 use bevy::core::FrameCount;
 use bevy::ecs::event::EventRegistry;
 use bevy::ecs::event::ShouldUpdateEvents;
+use bevy::input::gamepad::{GamepadAxisChangedEvent, GamepadEvent};
 use bevy::input::keyboard::Key;
 use bevy::input::keyboard::KeyboardInput;
 use bevy::input::ButtonState;
@@ -10,14 +9,35 @@ use bevy::input::InputPlugin;
 use bevy::prelude::*;
 use bevy::time::TimeUpdateStrategy;
 use bevy::utils::Duration;
-use bevy::window::WindowPlugin;
+use bevy::window::{PrimaryWindow, WindowPlugin};
 
 use leafwing_input_playback::input_capture::InputCapturePlugin;
 use leafwing_input_playback::input_capture::InputModesCaptured;
+use leafwing_input_playback::input_playback::inject_events;
 use leafwing_input_playback::input_playback::BeginInputPlayback;
+use leafwing_input_playback::input_playback::CursorOrigin;
 use leafwing_input_playback::input_playback::InputPlaybackPlugin;
 use leafwing_input_playback::input_playback::InputPlaybackSource;
+use leafwing_input_playback::input_playback::MaxEventsPerFrame;
+use leafwing_input_playback::input_playback::MissingWindowBehavior;
+use leafwing_input_playback::input_playback::PlaybackActivity;
+use leafwing_input_playback::input_playback::PlaybackBreakpointHit;
+use leafwing_input_playback::input_playback::PlaybackBreakpoints;
+use leafwing_input_playback::input_playback::PlaybackChannel;
+use leafwing_input_playback::input_playback::PlaybackControls;
+use leafwing_input_playback::input_playback::PlaybackDropRate;
+use leafwing_input_playback::input_playback::PlaybackGate;
+use leafwing_input_playback::input_playback::PlaybackLookahead;
+use leafwing_input_playback::input_playback::PlaybackProgress;
 use leafwing_input_playback::input_playback::PlaybackStrategy;
+use leafwing_input_playback::input_playback::PlaybackWarmup;
+use leafwing_input_playback::input_playback::PlaybackWindow;
+use leafwing_input_playback::input_playback::ReplayWithSnapshots;
+use leafwing_input_playback::input_playback::RetargetedInputs;
+use leafwing_input_playback::input_playback::SetPlaybackWindow;
+use leafwing_input_playback::input_playback::SyncGamepadAxes;
+#[cfg(feature = "test-support")]
+use leafwing_input_playback::test_support::{collect_playback, DeterministicPlaybackHarness};
 use leafwing_input_playback::timestamped_input::TimestampedInputs;
 
 const TEST_PRESS: KeyboardInput = KeyboardInput {
@@ -71,6 +91,21 @@ fn complex_timestamped_input() -> TimestampedInputs {
     inputs
 }
 
+#[test]
+fn next_event_gap() {
+    let mut inputs = complex_timestamped_input();
+
+    let mut gaps = Vec::new();
+    while let Some(gap) = inputs.next_event_gap() {
+        // Peeking does not advance the cursor
+        assert_eq!(inputs.next_event_gap(), Some(gap));
+        gaps.push(gap);
+        inputs.next();
+    }
+
+    assert_eq!(gaps, [0, 1, 1, 1, 0].map(Duration::from_secs).to_vec());
+}
+
 #[test]
 fn minimal_playback() {
     let mut app = playback_app();
@@ -104,7 +139,7 @@ fn minimal_playback() {
 #[test]
 fn capture_and_playback() {
     let mut app = playback_app();
-    app.add_plugins(InputCapturePlugin);
+    app.add_plugins(InputCapturePlugin::default());
 
     app.world_mut().trigger(BeginInputPlayback {
         playback_strategy: PlaybackStrategy::Paused,
@@ -228,6 +263,62 @@ fn playback_strategy_frame() {
     assert_eq!(timestamped_input.cursor, 5);
 }
 
+#[test]
+fn playback_activity() {
+    let mut inputs = TimestampedInputs::default();
+    inputs.send(FrameCount(0), Duration::from_secs(0), TEST_PRESS.into());
+    inputs.send(FrameCount(2), Duration::from_secs(0), TEST_RELEASE.into());
+
+    let mut app = playback_app();
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::FrameCount,
+        source: Some(InputPlaybackSource::from_inputs(inputs)),
+        ..Default::default()
+    });
+    app.world_mut().flush();
+
+    let mut activity = Vec::new();
+    for _ in 0..4 {
+        app.update();
+        activity.push(app.world().resource::<PlaybackActivity>().0);
+    }
+
+    // Only frames 0 and 2 have events to play back
+    assert_eq!(activity, [true, false, true, false]);
+}
+
+#[test]
+fn playback_warmup() {
+    let mut app = playback_app();
+
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::FrameCount,
+        source: Some(InputPlaybackSource::from_inputs(complex_timestamped_input())),
+        warmup: Some(PlaybackWarmup::Frames(3)),
+        ..Default::default()
+    });
+    app.world_mut().flush();
+
+    // Nothing is played back during frames 0, 1 and 2
+    for _ in 0..3 {
+        app.update();
+        let input_events = app.world().resource::<Events<KeyboardInput>>();
+        assert_eq!(input_events.iter_current_update_events().count(), 0);
+    }
+
+    // Playback then starts from the first recorded frame
+    app.update();
+    assert_eq!(app.world().resource::<FrameCount>().0, 4);
+    let timestamped_input = app.world().resource::<TimestampedInputs>();
+    assert_eq!(timestamped_input.cursor, 1);
+    let input = app.world().resource::<ButtonInput<KeyCode>>();
+    assert!(input.pressed(KeyCode::KeyF));
+
+    app.update();
+    let timestamped_input = app.world().resource::<TimestampedInputs>();
+    assert_eq!(timestamped_input.cursor, 2);
+}
+
 #[test]
 fn playback_strategy_frame_range_once() {
     let mut app = playback_app();
@@ -319,3 +410,2140 @@ fn playback_strategy_frame_range_loop() {
         PlaybackStrategy::FrameRangeLoop(FrameCount(2), FrameCount(5))
     );
 }
+
+#[test]
+fn playback_strategy_time_lookahead() {
+    const FRAME_DURATION: Duration = Duration::from_millis(16);
+
+    let mut inputs = TimestampedInputs::default();
+    inputs.send(FrameCount(1), FRAME_DURATION, TEST_PRESS.into());
+    inputs.send(FrameCount(2), FRAME_DURATION * 2, TEST_RELEASE.into());
+
+    // Returns the cursor after each of the first few updates
+    let cursor_per_update = |lookahead: Option<Duration>| {
+        let mut app = playback_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(FRAME_DURATION));
+        if let Some(lookahead) = lookahead {
+            app.insert_resource(PlaybackLookahead(lookahead));
+        }
+
+        app.world_mut().trigger(BeginInputPlayback {
+            playback_strategy: PlaybackStrategy::Time,
+            source: Some(InputPlaybackSource::from_inputs(inputs.clone())),
+            ..Default::default()
+        });
+        app.world_mut().flush();
+
+        (0..5)
+            .map(|_| {
+                app.update();
+                app.world().resource::<TimestampedInputs>().cursor
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let without_lookahead = cursor_per_update(None);
+    let with_lookahead = cursor_per_update(Some(FRAME_DURATION));
+
+    // Each event fires one frame sooner with the lookahead
+    assert_eq!(without_lookahead, vec![0, 1, 2, 2, 2]);
+    assert_eq!(with_lookahead, vec![1, 2, 2, 2, 2]);
+}
+
+#[test]
+fn sync_gamepad_axes() {
+    // Returns the replayed stick value, as seen by the `Gamepad` component
+    let replayed_stick_value = |sync: bool| {
+        let mut app = playback_app();
+        if sync {
+            app.insert_resource(SyncGamepadAxes);
+        }
+        let gamepad = app.world_mut().spawn(Gamepad::default()).id();
+
+        let mut inputs = TimestampedInputs::default();
+        inputs.send(
+            FrameCount(0),
+            Duration::ZERO,
+            GamepadEvent::Axis(GamepadAxisChangedEvent::new(
+                gamepad,
+                GamepadAxis::LeftStickX,
+                0.5,
+            ))
+            .into(),
+        );
+
+        app.world_mut().trigger(BeginInputPlayback {
+            playback_strategy: PlaybackStrategy::FrameCount,
+            source: Some(InputPlaybackSource::from_inputs(inputs)),
+            ..Default::default()
+        });
+        app.world_mut().flush();
+        app.update();
+
+        app.world()
+            .get::<Gamepad>(gamepad)
+            .unwrap()
+            .get(GamepadAxis::LeftStickX)
+    };
+
+    assert_eq!(replayed_stick_value(true), Some(0.5));
+    // Without syncing, the stick stays at its default value
+    assert_eq!(replayed_stick_value(false), Some(0.0));
+}
+
+#[cfg(not(feature = "file"))]
+#[test]
+fn in_memory_capture_and_playback_without_file_feature() {
+    use leafwing_input_playback::input_capture::{BeginInputCapture, EndInputCapture};
+
+    let mut app = playback_app();
+    app.add_plugins(InputCapturePlugin::default());
+
+    app.world_mut().trigger(BeginInputCapture::default());
+    let mut input_events = app.world_mut().resource_mut::<Events<KeyboardInput>>();
+    input_events.send(TEST_PRESS);
+    app.update();
+
+    app.world_mut().trigger(EndInputCapture);
+    app.world_mut().flush();
+    let captured_inputs = app
+        .world_mut()
+        .remove_resource::<TimestampedInputs>()
+        .unwrap();
+    assert_eq!(captured_inputs.len(), 1);
+
+    let mut app = playback_app();
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::FrameCount,
+        source: Some(InputPlaybackSource::from_inputs(captured_inputs)),
+        ..Default::default()
+    });
+    app.world_mut().flush();
+    app.update();
+
+    let input = app.world().resource::<ButtonInput<KeyCode>>();
+    assert!(input.pressed(TEST_PRESS.key_code));
+}
+
+#[test]
+// `BeginInputPlayback::resume` only exists with the `file` feature
+#[allow(clippy::needless_update)]
+fn retarget_only_cursor_events() {
+    let mut app = playback_app();
+    let recorded_window = app.world_mut().spawn_empty().id();
+    let primary_window = app
+        .world_mut()
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .single(app.world());
+
+    let mut inputs = TimestampedInputs::default();
+    inputs.send(
+        FrameCount(0),
+        Duration::ZERO,
+        KeyboardInput {
+            window: recorded_window,
+            ..TEST_PRESS
+        }
+        .into(),
+    );
+    inputs.send(
+        FrameCount(0),
+        Duration::ZERO,
+        CursorMoved {
+            window: recorded_window,
+            position: Vec2::new(10.0, 20.0),
+            delta: None,
+        }
+        .into(),
+    );
+
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::FrameCount,
+        source: Some(InputPlaybackSource::from_inputs(inputs)),
+        playback_window: Some(PlaybackWindow::PrimaryWindow),
+        retargeted_inputs: RetargetedInputs {
+            mouse_motion: true,
+            ..RetargetedInputs::NONE
+        },
+        ..Default::default()
+    });
+    app.world_mut().flush();
+    app.update();
+
+    let cursor_events = app.world().resource::<Events<CursorMoved>>();
+    let cursor_event = cursor_events.iter_current_update_events().next().unwrap();
+    assert_eq!(cursor_event.window, primary_window);
+
+    let keyboard_events = app.world().resource::<Events<KeyboardInput>>();
+    let keyboard_event = keyboard_events.iter_current_update_events().next().unwrap();
+    assert_eq!(keyboard_event.window, recorded_window);
+
+    // The cursor position was set on the retargeted window
+    let window = app.world().get::<Window>(primary_window).unwrap();
+    assert_eq!(window.cursor_position(), Some(Vec2::new(10.0, 20.0)));
+}
+
+#[test]
+fn playback_at_recorded_frame_rate() {
+    // Captured at 60 fps, played back at 144 fps
+    let mut inputs = TimestampedInputs::default();
+    inputs.send(FrameCount(0), Duration::ZERO, TEST_PRESS.into());
+    inputs.send(FrameCount(60), Duration::from_secs(1), TEST_RELEASE.into());
+
+    let mut app = playback_app();
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+        1. / 144.,
+    )));
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::RecordedFrameRate,
+        source: Some(InputPlaybackSource::from_inputs(inputs)),
+        ..Default::default()
+    });
+    app.world_mut().flush();
+
+    app.update();
+    assert_eq!(app.world().resource::<TimestampedInputs>().cursor, 1);
+
+    // Frame-based playback would have finished after 60 frames,
+    // but less than a second of real time has passed
+    for _ in 0..120 {
+        app.update();
+    }
+    assert_eq!(app.world().resource::<TimestampedInputs>().cursor, 1);
+
+    // After a second has passed, the whole recording has been played
+    for _ in 0..30 {
+        app.update();
+    }
+    assert_eq!(app.world().resource::<TimestampedInputs>().cursor, 2);
+}
+
+#[test]
+fn inject_events_immediately() {
+    let mut app = playback_app();
+    app.update();
+
+    inject_events(app.world_mut(), [TEST_PRESS.into()]);
+
+    // The event is available without waiting for the next update
+    let keyboard_events = app.world().resource::<Events<KeyboardInput>>();
+    let mut reader = keyboard_events.get_cursor();
+    let injected: Vec<&KeyboardInput> = reader.read(keyboard_events).collect();
+    assert_eq!(injected, vec![&TEST_PRESS]);
+}
+
+#[test]
+fn retarget_missing_window_to_primary() {
+    let mut app = playback_app();
+    app.insert_resource(MissingWindowBehavior::RetargetPrimary);
+    let primary_window = app
+        .world_mut()
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .single(app.world());
+    let missing_window = app.world_mut().spawn_empty().id();
+    app.world_mut().despawn(missing_window);
+
+    let mut inputs = TimestampedInputs::default();
+    inputs.send(
+        FrameCount(0),
+        Duration::ZERO,
+        CursorMoved {
+            window: missing_window,
+            position: Vec2::new(10.0, 20.0),
+            delta: None,
+        }
+        .into(),
+    );
+
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::FrameCount,
+        source: Some(InputPlaybackSource::from_inputs(inputs)),
+        ..Default::default()
+    });
+    app.world_mut().flush();
+    app.update();
+
+    let cursor_events = app.world().resource::<Events<CursorMoved>>();
+    let cursor_event = cursor_events.iter_current_update_events().next().unwrap();
+    assert_eq!(cursor_event.window, primary_window);
+
+    let window = app.world().get::<Window>(primary_window).unwrap();
+    assert_eq!(window.cursor_position(), Some(Vec2::new(10.0, 20.0)));
+}
+
+#[test]
+fn switch_playback_window() {
+    let mut app = playback_app();
+    let primary_window = app
+        .world_mut()
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .single(app.world());
+    let secondary_window = app.world_mut().spawn(Window::default()).id();
+
+    let mut inputs = TimestampedInputs::default();
+    inputs.send(FrameCount(0), Duration::ZERO, TEST_PRESS.into());
+    inputs.send(FrameCount(1), Duration::ZERO, TEST_RELEASE.into());
+
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::FrameCount,
+        source: Some(InputPlaybackSource::from_inputs(inputs)),
+        playback_window: Some(PlaybackWindow::PrimaryWindow),
+        ..Default::default()
+    });
+    app.world_mut().flush();
+
+    let mut reader = app.world().resource::<Events<KeyboardInput>>().get_cursor();
+    let mut played_windows = Vec::new();
+    for _ in 0..2 {
+        app.update();
+        let keyboard_events = app.world().resource::<Events<KeyboardInput>>();
+        played_windows.extend(reader.read(keyboard_events).map(|event| event.window));
+
+        // Move the replay to the other window partway through
+        app.world_mut()
+            .trigger(SetPlaybackWindow(PlaybackWindow::Window(secondary_window)));
+    }
+
+    assert_eq!(played_windows, vec![primary_window, secondary_window]);
+}
+
+#[test]
+fn max_events_per_frame() {
+    let mut inputs = TimestampedInputs::default();
+    for _ in 0..5 {
+        inputs.send(FrameCount(0), Duration::ZERO, TEST_PRESS.into());
+    }
+    inputs.send(FrameCount(1), Duration::ZERO, TEST_RELEASE.into());
+
+    let mut app = playback_app();
+    app.insert_resource(MaxEventsPerFrame(2));
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::FrameCount,
+        source: Some(InputPlaybackSource::from_inputs(inputs)),
+        ..Default::default()
+    });
+    app.world_mut().flush();
+
+    let mut reader = app.world().resource::<Events<KeyboardInput>>().get_cursor();
+    let mut played_per_frame = Vec::new();
+    for _ in 0..4 {
+        app.update();
+        let keyboard_events = app.world().resource::<Events<KeyboardInput>>();
+        let played: Vec<ButtonState> = reader
+            .read(keyboard_events)
+            .map(|event| event.state)
+            .collect();
+        played_per_frame.push(played);
+    }
+
+    // The release is recorded on the second frame, but waits behind the deferred presses
+    use ButtonState::*;
+    assert_eq!(
+        played_per_frame,
+        vec![
+            vec![Pressed, Pressed],
+            vec![Pressed, Pressed],
+            vec![Pressed, Released],
+            vec![],
+        ]
+    );
+}
+
+#[test]
+fn playback_drop_rate_is_reproducible() {
+    let played_with_drops = || {
+        let mut inputs = TimestampedInputs::default();
+        for frame in 0..20 {
+            inputs.send(FrameCount(2 * frame), Duration::ZERO, TEST_PRESS.into());
+            inputs.send(
+                FrameCount(2 * frame + 1),
+                Duration::ZERO,
+                TEST_RELEASE.into(),
+            );
+        }
+
+        let mut app = playback_app();
+        app.insert_resource(PlaybackDropRate::new(0.5, 42));
+        app.world_mut().trigger(BeginInputPlayback {
+            playback_strategy: PlaybackStrategy::FrameCount,
+            source: Some(InputPlaybackSource::from_inputs(inputs)),
+            ..Default::default()
+        });
+        app.world_mut().flush();
+
+        let mut reader = app.world().resource::<Events<KeyboardInput>>().get_cursor();
+        let mut played = Vec::new();
+        for _ in 0..45 {
+            app.update();
+            let keyboard_events = app.world().resource::<Events<KeyboardInput>>();
+            played.extend(reader.read(keyboard_events).map(|event| event.state));
+        }
+        played
+    };
+
+    let first_run = played_with_drops();
+    assert_eq!(first_run, played_with_drops());
+    assert!(!first_run.is_empty() && first_run.len() < 40);
+    // Releases of dropped presses are dropped too, so every release follows a press
+    let mut held = false;
+    for state in first_run {
+        if state == ButtonState::Released {
+            assert!(held);
+        }
+        held = state == ButtonState::Pressed;
+    }
+}
+
+#[test]
+fn pause_at_breakpoint() {
+    let mut app = playback_app();
+    app.insert_resource(PlaybackBreakpoints([FrameCount(2)].into_iter().collect()));
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::FrameCount,
+        source: Some(InputPlaybackSource::from_inputs(complex_timestamped_input())),
+        ..Default::default()
+    });
+    app.world_mut().flush();
+
+    let mut reader = app.world().resource::<Events<KeyboardInput>>().get_cursor();
+    let mut breakpoint_reader = app
+        .world()
+        .resource::<Events<PlaybackBreakpointHit>>()
+        .get_cursor();
+    let mut played = 0;
+    let mut hits = Vec::new();
+    for _ in 0..5 {
+        app.update();
+        played += reader
+            .read(app.world().resource::<Events<KeyboardInput>>())
+            .count();
+        hits.extend(
+            breakpoint_reader
+                .read(app.world().resource::<Events<PlaybackBreakpointHit>>())
+                .copied(),
+        );
+    }
+
+    // Frames 0 to 2 are played back, but not frame 3
+    assert_eq!(played, 4);
+    assert_eq!(
+        *app.world().resource::<PlaybackStrategy>(),
+        PlaybackStrategy::Paused
+    );
+    assert_eq!(hits, vec![PlaybackBreakpointHit(FrameCount(2))]);
+
+    // Resuming continues past the breakpoint
+    app.insert_resource(PlaybackStrategy::FrameCount);
+    app.update();
+    played += reader
+        .read(app.world().resource::<Events<KeyboardInput>>())
+        .count();
+    assert_eq!(played, 5);
+}
+
+#[test]
+fn pause_at_breakpoint_without_events() {
+    let mut inputs = TimestampedInputs::default();
+    inputs.send(FrameCount(0), Duration::ZERO, TEST_PRESS.into());
+    inputs.send(FrameCount(4), Duration::ZERO, TEST_RELEASE.into());
+
+    let mut app = playback_app();
+    app.insert_resource(PlaybackBreakpoints([FrameCount(2)].into_iter().collect()));
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::FrameCount,
+        source: Some(InputPlaybackSource::from_inputs(inputs)),
+        ..Default::default()
+    });
+    app.world_mut().flush();
+
+    let mut breakpoint_reader = app
+        .world()
+        .resource::<Events<PlaybackBreakpointHit>>()
+        .get_cursor();
+    let mut hits = Vec::new();
+    for _ in 0..3 {
+        app.update();
+        hits.extend(
+            breakpoint_reader
+                .read(app.world().resource::<Events<PlaybackBreakpointHit>>())
+                .copied(),
+        );
+    }
+
+    // Nothing was recorded on frame 2, but playback still pauses there
+    assert_eq!(hits, vec![PlaybackBreakpointHit(FrameCount(2))]);
+    assert_eq!(
+        *app.world().resource::<PlaybackStrategy>(),
+        PlaybackStrategy::Paused
+    );
+    assert_eq!(app.world().resource::<TimestampedInputs>().cursor, 1);
+}
+
+#[test]
+fn breakpoints_hold_back_later_frames() {
+    let mut app = playback_app();
+    app.insert_resource(PlaybackBreakpoints([FrameCount(1)].into_iter().collect()));
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::Immediate,
+        source: Some(InputPlaybackSource::from_inputs(complex_timestamped_input())),
+        ..Default::default()
+    });
+    app.world_mut().flush();
+    app.update();
+
+    // Only frames 0 and 1 are played back, and the cursor is left on frame 2
+    let keyboard_events = app.world().resource::<Events<KeyboardInput>>();
+    assert_eq!(keyboard_events.iter_current_update_events().count(), 2);
+    assert_eq!(app.world().resource::<TimestampedInputs>().cursor, 2);
+
+    // Resuming plays back the events that were held back
+    app.insert_resource(PlaybackStrategy::Immediate);
+    app.update();
+    let keyboard_events = app.world().resource::<Events<KeyboardInput>>();
+    assert_eq!(keyboard_events.iter_current_update_events().count(), 3);
+}
+
+#[test]
+fn immediate_playback() {
+    let mut app = playback_app();
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::Immediate,
+        source: Some(InputPlaybackSource::from_inputs(complex_timestamped_input())),
+        ..Default::default()
+    });
+    app.world_mut().flush();
+    app.update();
+
+    let keyboard_events = app.world().resource::<Events<KeyboardInput>>();
+    assert_eq!(keyboard_events.len(), complex_timestamped_input().len());
+    assert_eq!(
+        *app.world().resource::<PlaybackStrategy>(),
+        PlaybackStrategy::Paused
+    );
+}
+
+#[test]
+fn playback_channel() {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let mut app = playback_app();
+    app.insert_resource(PlaybackChannel::new(sender));
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::FrameCount,
+        source: Some(InputPlaybackSource::from_inputs(complex_timestamped_input())),
+        ..Default::default()
+    });
+    app.world_mut().flush();
+
+    let mut reader = app.world().resource::<Events<KeyboardInput>>().get_cursor();
+    let mut sent_to_app = 0;
+    for _ in 0..5 {
+        app.update();
+        let keyboard_events = app.world().resource::<Events<KeyboardInput>>();
+        sent_to_app += reader.read(keyboard_events).count();
+    }
+
+    let expected: Vec<_> = complex_timestamped_input()
+        .events
+        .into_iter()
+        .map(|event| event.input_event)
+        .collect();
+    assert_eq!(receiver.try_iter().collect::<Vec<_>>(), expected);
+    // Events are still sent to the app as well
+    assert_eq!(sent_to_app, expected.len());
+}
+
+#[test]
+fn cursor_position_per_window() {
+    let mut app = playback_app();
+    let primary_window = app
+        .world_mut()
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .single(app.world());
+    let secondary_window = app.world_mut().spawn(Window::default()).id();
+
+    let cursor_moved =
+        |window: Entity, x: f32| -> leafwing_input_playback::timestamped_input::InputEvent {
+            CursorMoved {
+                window,
+                position: Vec2::new(x, 20.0),
+                delta: None,
+            }
+            .into()
+        };
+    let mut inputs = TimestampedInputs::default();
+    inputs.send(
+        FrameCount(0),
+        Duration::ZERO,
+        cursor_moved(primary_window, 1.0),
+    );
+    inputs.send(
+        FrameCount(0),
+        Duration::ZERO,
+        cursor_moved(secondary_window, 2.0),
+    );
+    inputs.send(
+        FrameCount(1),
+        Duration::ZERO,
+        cursor_moved(primary_window, 3.0),
+    );
+    inputs.send(
+        FrameCount(1),
+        Duration::ZERO,
+        cursor_moved(secondary_window, 4.0),
+    );
+    inputs.send(
+        FrameCount(1),
+        Duration::ZERO,
+        cursor_moved(primary_window, 5.0),
+    );
+
+    let cursor_position =
+        |app: &App, window: Entity| app.world().get::<Window>(window).unwrap().cursor_position();
+
+    // Each window keeps the position of the last cursor event recorded in it
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::FrameCount,
+        source: Some(InputPlaybackSource::from_inputs(inputs)),
+        ..Default::default()
+    });
+    app.world_mut().flush();
+    app.update();
+    app.update();
+    assert_eq!(
+        cursor_position(&app, primary_window),
+        Some(Vec2::new(5.0, 20.0))
+    );
+    assert_eq!(
+        cursor_position(&app, secondary_window),
+        Some(Vec2::new(4.0, 20.0))
+    );
+
+    // Once retargeted, every cursor event moves the cursor of the playback window only
+    let mut app = playback_app();
+    let primary_window = app
+        .world_mut()
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .single(app.world());
+    let secondary_window = app.world_mut().spawn(Window::default()).id();
+    let mut retargeted = TimestampedInputs::default();
+    retargeted.send(
+        FrameCount(0),
+        Duration::ZERO,
+        cursor_moved(primary_window, 1.0),
+    );
+    retargeted.send(
+        FrameCount(0),
+        Duration::ZERO,
+        cursor_moved(secondary_window, 2.0),
+    );
+    retargeted.send(
+        FrameCount(1),
+        Duration::ZERO,
+        cursor_moved(secondary_window, 3.0),
+    );
+    retargeted.send(
+        FrameCount(1),
+        Duration::ZERO,
+        cursor_moved(primary_window, 4.0),
+    );
+
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::FrameCount,
+        source: Some(InputPlaybackSource::from_inputs(retargeted)),
+        playback_window: Some(PlaybackWindow::Window(secondary_window)),
+        retargeted_inputs: RetargetedInputs::ALL,
+        ..Default::default()
+    });
+    app.world_mut().flush();
+    app.update();
+    app.update();
+    assert_eq!(cursor_position(&app, primary_window), None);
+    assert_eq!(
+        cursor_position(&app, secondary_window),
+        Some(Vec2::new(4.0, 20.0))
+    );
+}
+
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq)]
+struct Toggle(bool);
+
+fn toggle_on_press(keyboard_input: Res<ButtonInput<KeyCode>>, mut toggle: ResMut<Toggle>) {
+    if keyboard_input.just_pressed(KeyCode::KeyF) {
+        toggle.0 = !toggle.0;
+    }
+}
+
+#[test]
+fn replay_with_snapshots() {
+    let mut app = playback_app();
+    app.init_resource::<Toggle>()
+        .add_systems(Update, toggle_on_press);
+
+    let mut inputs = TimestampedInputs::default();
+    inputs.send(FrameCount(1), Duration::ZERO, TEST_PRESS.into());
+    inputs.send(FrameCount(2), Duration::ZERO, TEST_RELEASE.into());
+    inputs.send(FrameCount(3), Duration::ZERO, TEST_PRESS.into());
+    inputs.send(FrameCount(4), Duration::ZERO, TEST_RELEASE.into());
+
+    let replay = ReplayWithSnapshots::new([FrameCount(3), FrameCount(2)], |world: &World| {
+        *world.resource::<Toggle>()
+    });
+    let snapshots = replay.run(&mut app, inputs);
+
+    assert_eq!(
+        snapshots,
+        vec![
+            (FrameCount(2), Toggle(true)),
+            (FrameCount(3), Toggle(false))
+        ]
+    );
+}
+
+#[cfg(feature = "file")]
+#[test]
+fn extra_mouse_buttons_round_trip() {
+    use bevy::input::mouse::MouseButtonInput;
+    use leafwing_input_playback::input_capture::{BeginInputCapture, EndInputCapture};
+
+    // Mouse back buttons are commonly reported as `Other(8)`
+    const BACK_BUTTON: MouseButton = MouseButton::Other(8);
+    let press = MouseButtonInput {
+        button: BACK_BUTTON,
+        state: ButtonState::Pressed,
+        window: Entity::PLACEHOLDER,
+    };
+    let release = MouseButtonInput {
+        state: ButtonState::Released,
+        ..press
+    };
+
+    let path = std::env::temp_dir().join("leafwing_input_playback_extra_mouse_buttons.ron");
+    let path = path.to_str().unwrap().to_string();
+
+    let mut app = playback_app();
+    app.add_plugins(InputCapturePlugin::default());
+    app.world_mut().trigger(BeginInputCapture {
+        filepath: Some(path.clone().into()),
+        ..Default::default()
+    });
+    let mut input_events = app.world_mut().resource_mut::<Events<MouseButtonInput>>();
+    input_events.send(press);
+    app.update();
+    let mut input_events = app.world_mut().resource_mut::<Events<MouseButtonInput>>();
+    input_events.send(release);
+    app.update();
+    app.world_mut().trigger(EndInputCapture);
+    app.world_mut().flush();
+
+    let mut app = playback_app();
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::FrameCount,
+        source: Some(InputPlaybackSource::from_file(path.clone())),
+        ..Default::default()
+    });
+    app.world_mut().flush();
+    std::fs::remove_file(path).unwrap();
+
+    let played_back_events = |app: &App| {
+        let events = app.world().resource::<Events<MouseButtonInput>>();
+        events
+            .iter_current_update_events()
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+
+    app.update();
+    assert_eq!(played_back_events(&app), vec![press]);
+    let input = app.world().resource::<ButtonInput<MouseButton>>();
+    assert!(input.pressed(BACK_BUTTON));
+
+    app.update();
+    assert_eq!(played_back_events(&app), vec![release]);
+    let input = app.world().resource::<ButtonInput<MouseButton>>();
+    assert!(!input.pressed(BACK_BUTTON));
+    assert!(input.just_released(BACK_BUTTON));
+}
+
+#[cfg(feature = "file")]
+#[test]
+fn resume_playback_from_checkpoint() {
+    use leafwing_input_playback::input_playback::{PersistPlaybackProgress, PlaybackCheckpoint};
+
+    let path = std::env::temp_dir().join("leafwing_input_playback_resume_checkpoint.ron");
+    let mut inputs = TimestampedInputs::default();
+    for frame in 0..4 {
+        let event = if frame % 2 == 0 {
+            TEST_PRESS
+        } else {
+            TEST_RELEASE
+        };
+        inputs.send(FrameCount(frame), Duration::ZERO, event.into());
+    }
+
+    let mut app = playback_app();
+    app.insert_resource(PersistPlaybackProgress {
+        path: path.clone(),
+        interval: 2,
+    });
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::FrameCount,
+        source: Some(InputPlaybackSource::from_inputs(inputs.clone())),
+        ..Default::default()
+    });
+    app.world_mut().flush();
+    app.update();
+    // Checkpoints are only saved every `interval` frames
+    assert!(!path.exists());
+    app.update();
+    assert_eq!(app.world().resource::<TimestampedInputs>().cursor, 2);
+
+    let checkpoint = PlaybackCheckpoint::load(&path).unwrap();
+    assert_eq!(checkpoint.cursor, 2);
+    assert_eq!(
+        checkpoint.recording_hash,
+        PlaybackCheckpoint::hash_recording(&inputs)
+    );
+
+    // A different recording with the same number of events does not resume from the checkpoint
+    let mut different_inputs = inputs.clone();
+    different_inputs.events[3].frame = FrameCount(10);
+    let mut app = playback_app();
+    app.insert_resource(PersistPlaybackProgress::new(path.clone()));
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::FrameCount,
+        source: Some(InputPlaybackSource::from_inputs(different_inputs)),
+        resume: true,
+        ..Default::default()
+    });
+    app.world_mut().flush();
+    assert_eq!(app.world().resource::<TimestampedInputs>().cursor, 0);
+
+    // Restart the app, resuming from the saved checkpoint
+    drop(app);
+    let mut app = playback_app();
+    app.insert_resource(PersistPlaybackProgress::new(path.clone()));
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::FrameCount,
+        source: Some(InputPlaybackSource::from_inputs(inputs)),
+        resume: true,
+        ..Default::default()
+    });
+    app.world_mut().flush();
+    std::fs::remove_file(path).unwrap();
+
+    assert_eq!(app.world().resource::<TimestampedInputs>().cursor, 2);
+}
+
+#[cfg(feature = "file")]
+#[test]
+fn resume_time_playback_from_checkpoint() {
+    use leafwing_input_playback::input_playback::{PersistPlaybackProgress, PlaybackTimeOffset};
+
+    const FRAME_DURATION: Duration = Duration::from_millis(100);
+
+    let path = std::env::temp_dir().join("leafwing_input_playback_resume_time_checkpoint.ron");
+    let mut inputs = TimestampedInputs::default();
+    for frame in 0..20 {
+        let event = if frame % 2 == 0 {
+            TEST_PRESS
+        } else {
+            TEST_RELEASE
+        };
+        inputs.send(FrameCount(frame), FRAME_DURATION * frame, event.into());
+    }
+
+    let begin_playback = |app: &mut App, resume: bool| {
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(FRAME_DURATION))
+            .insert_resource(PersistPlaybackProgress {
+                path: path.clone(),
+                interval: 1,
+            });
+        app.world_mut().trigger(BeginInputPlayback {
+            playback_strategy: PlaybackStrategy::Time,
+            source: Some(InputPlaybackSource::from_inputs(inputs.clone())),
+            resume,
+            ..Default::default()
+        });
+        app.world_mut().flush();
+    };
+
+    let mut app = playback_app();
+    begin_playback(&mut app, false);
+    for _ in 0..11 {
+        app.update();
+    }
+    let cursor = app.world().resource::<TimestampedInputs>().cursor;
+    assert!(cursor >= 10);
+
+    // After a restart, playback continues from the saved time rather than waiting for the app to catch up
+    drop(app);
+    let mut app = playback_app();
+    begin_playback(&mut app, true);
+    assert_eq!(app.world().resource::<TimestampedInputs>().cursor, cursor);
+    assert!(app.world().resource::<PlaybackTimeOffset>().0 >= FRAME_DURATION * 9);
+    app.update();
+    app.update();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(app.world().resource::<TimestampedInputs>().cursor > cursor);
+}
+
+#[test]
+fn respect_recorded_duration() {
+    const FRAME_DURATION: Duration = Duration::from_millis(100);
+
+    // Recorded at a low frame rate, so that frame 2 was only reached after 3 seconds
+    let mut inputs = TimestampedInputs::default();
+    inputs.send(FrameCount(0), Duration::ZERO, TEST_PRESS.into());
+    inputs.send(FrameCount(2), Duration::from_secs(3), TEST_RELEASE.into());
+
+    // Returns the number of updates taken to play back the whole recording
+    let updates_to_finish = |respect_recorded_duration: bool| {
+        let mut app = playback_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(FRAME_DURATION));
+        app.world_mut().trigger(BeginInputPlayback {
+            playback_strategy: PlaybackStrategy::FrameCount,
+            source: Some(InputPlaybackSource::from_inputs(inputs.clone())),
+            respect_recorded_duration,
+            ..Default::default()
+        });
+        app.world_mut().flush();
+
+        let mut updates = 0;
+        while app.world().resource::<TimestampedInputs>().cursor < 2 {
+            app.update();
+            updates += 1;
+            assert!(updates < 100);
+        }
+        updates
+    };
+
+    assert_eq!(updates_to_finish(false), 3);
+
+    // The replay takes approximately the recorded 3 seconds
+    let updates = updates_to_finish(true);
+    let replay_duration = FRAME_DURATION * updates;
+    assert!(replay_duration >= Duration::from_secs(3));
+    assert!(replay_duration <= Duration::from_secs(3) + FRAME_DURATION * 2);
+}
+
+#[test]
+fn warmup_delays_recorded_duration() {
+    const FRAME_DURATION: Duration = Duration::from_millis(100);
+    const WARMUP_FRAMES: u32 = 5;
+
+    let mut inputs = TimestampedInputs::default();
+    inputs.send(FrameCount(0), Duration::ZERO, TEST_PRESS.into());
+    inputs.send(FrameCount(2), Duration::from_secs(3), TEST_RELEASE.into());
+
+    let mut app = playback_app();
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(FRAME_DURATION));
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::FrameCount,
+        source: Some(InputPlaybackSource::from_inputs(inputs)),
+        respect_recorded_duration: true,
+        warmup: Some(PlaybackWarmup::Frames(WARMUP_FRAMES)),
+        ..Default::default()
+    });
+    app.world_mut().flush();
+
+    for _ in 0..WARMUP_FRAMES {
+        app.update();
+        assert_eq!(app.world().resource::<TimestampedInputs>().cursor, 0);
+    }
+
+    let mut updates = WARMUP_FRAMES;
+    while app.world().resource::<TimestampedInputs>().cursor < 2 {
+        app.update();
+        updates += 1;
+        assert!(updates < 100);
+    }
+
+    // The recorded 3 seconds are replayed in full after the warm-up, rather than overlapping it
+    let replay_duration = FRAME_DURATION * updates;
+    let expected = FRAME_DURATION * WARMUP_FRAMES + Duration::from_secs(3);
+    assert!(replay_duration >= expected);
+    assert!(replay_duration <= expected + FRAME_DURATION * 2);
+}
+
+#[test]
+fn idle_markers_anchor_frame_playback() {
+    use leafwing_input_playback::timestamped_input::InputEvent;
+
+    const FRAME_DURATION: Duration = Duration::from_millis(100);
+
+    // A long idle period, which took 5 seconds but only 10 frames to record
+    let mut with_markers = TimestampedInputs::default();
+    with_markers.send(FrameCount(0), Duration::ZERO, TEST_PRESS.into());
+    with_markers.send(
+        FrameCount(10),
+        Duration::from_secs(5),
+        InputEvent::IdleMarker,
+    );
+    with_markers.send(
+        FrameCount(12),
+        Duration::from_millis(5200),
+        TEST_RELEASE.into(),
+    );
+    let mut without_markers = with_markers.clone();
+    without_markers.events.remove(1);
+
+    // Returns the number of updates taken to play back the whole recording
+    let updates_to_finish = |inputs: &TimestampedInputs| {
+        let mut app = playback_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(FRAME_DURATION));
+        app.world_mut().trigger(BeginInputPlayback {
+            playback_strategy: PlaybackStrategy::FrameCount,
+            source: Some(InputPlaybackSource::from_inputs(inputs.clone())),
+            ..Default::default()
+        });
+        app.world_mut().flush();
+
+        let mut updates = 0;
+        while app.world().resource::<TimestampedInputs>().cursor < inputs.len() {
+            app.update();
+            updates += 1;
+            assert!(updates < 100);
+        }
+        updates
+    };
+
+    // Without markers, the idle period replays as fast as its frames
+    assert_eq!(updates_to_finish(&without_markers), 13);
+
+    // With markers, playback waits at the marker for the recorded 5 seconds,
+    // and the release still follows it by the recorded 2 frames
+    let updates = updates_to_finish(&with_markers);
+    let replay_duration = FRAME_DURATION * updates;
+    assert!(replay_duration >= Duration::from_millis(5200));
+    assert!(replay_duration <= Duration::from_millis(5200) + FRAME_DURATION * 2);
+}
+
+#[test]
+fn playback_controls() {
+    use bevy::ecs::system::RunSystemOnce;
+
+    let mut app = playback_app();
+    let mut inputs = TimestampedInputs::default();
+    for frame in 0..4 {
+        let event = if frame % 2 == 0 {
+            TEST_PRESS
+        } else {
+            TEST_RELEASE
+        };
+        inputs.send(FrameCount(frame), Duration::ZERO, event.into());
+    }
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::Paused,
+        source: Some(InputPlaybackSource::from_inputs(inputs)),
+        ..Default::default()
+    });
+    app.world_mut().flush();
+
+    let control = |app: &mut App, f: fn(&mut PlaybackControls)| {
+        app.world_mut()
+            .run_system_once(move |mut controls: PlaybackControls| {
+                f(&mut controls);
+                controls.cursor()
+            })
+            .unwrap()
+    };
+
+    app.update();
+    app.update();
+    assert_eq!(control(&mut app, |_| ()), 0);
+
+    control(&mut app, |controls| {
+        controls.resume(PlaybackStrategy::FrameCount);
+    });
+    app.update();
+    // Frames 0, 1 and 2 have been played back
+    assert_eq!(control(&mut app, |_| ()), 3);
+
+    control(&mut app, |controls| controls.pause());
+    app.update();
+    assert_eq!(control(&mut app, |_| ()), 3);
+
+    // Seeking is only supported by strategies that are driven by the playback progress
+    let cursor = control(&mut app, |controls| {
+        assert_eq!(controls.strategy(), PlaybackStrategy::Paused);
+        assert!(!controls.seek_to_frame(FrameCount(1)));
+    });
+    assert_eq!(cursor, 3);
+
+    let cursor = control(&mut app, |controls| {
+        controls.resume(PlaybackStrategy::FrameRangeOnce(
+            FrameCount(0),
+            FrameCount(3),
+        ));
+        assert!(controls.seek_to_frame(FrameCount(1)));
+    });
+    assert_eq!(cursor, 1);
+
+    let cursor = control(&mut app, |controls| {
+        controls.seek_to_frame(FrameCount(2));
+        assert_eq!(controls.progress().elapsed_frames, FrameCount(2));
+        controls.restart();
+        assert_eq!(*controls.progress(), PlaybackProgress::default());
+    });
+    assert_eq!(cursor, 0);
+
+    // After seeking, playback continues from the sought frame rather than replaying the skipped events
+    control(&mut app, |controls| {
+        controls.seek_to_frame(FrameCount(2));
+    });
+    app.update();
+    assert_eq!(control(&mut app, |_| ()), 3);
+}
+
+#[test]
+fn accumulated_mouse_round_trip() {
+    use bevy::input::mouse::{AccumulatedMouseMotion, MouseMotion};
+    use leafwing_input_playback::input_capture::{BeginInputCapture, EndInputCapture};
+
+    let mut capture_app = App::new();
+    capture_app.add_plugins((
+        MinimalPlugins,
+        WindowPlugin::default(),
+        InputPlugin,
+        InputCapturePlugin::default(),
+    ));
+    capture_app.world_mut().trigger(BeginInputCapture {
+        capture_accumulated_mouse: true,
+        ..Default::default()
+    });
+
+    let deltas = [Vec2::new(1., 2.), Vec2::ZERO, Vec2::new(-3., 0.5)];
+    for delta in deltas {
+        if delta != Vec2::ZERO {
+            // Split across two events, which are summed into the accumulated resource
+            capture_app
+                .world_mut()
+                .send_event(MouseMotion { delta: delta / 2. });
+            capture_app
+                .world_mut()
+                .send_event(MouseMotion { delta: delta / 2. });
+        }
+        capture_app.update();
+        assert_eq!(
+            capture_app
+                .world()
+                .resource::<AccumulatedMouseMotion>()
+                .delta,
+            delta
+        );
+    }
+    capture_app.world_mut().trigger(EndInputCapture);
+    let inputs = capture_app
+        .world_mut()
+        .remove_resource::<TimestampedInputs>()
+        .unwrap();
+    // Idle frames are not recorded
+    assert_eq!(inputs.len(), 2);
+
+    let mut app = playback_app();
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::FrameCount,
+        source: Some(InputPlaybackSource::from_inputs(inputs)),
+        ..Default::default()
+    });
+    app.world_mut().flush();
+
+    for delta in deltas {
+        app.update();
+        assert_eq!(
+            app.world().resource::<AccumulatedMouseMotion>().delta,
+            delta
+        );
+    }
+}
+
+#[cfg(feature = "test-support")]
+fn timed_keypresses() -> TimestampedInputs {
+    TimestampedInputs::from_timed((0..4).map(|i| {
+        let event = if i % 2 == 0 { TEST_PRESS } else { TEST_RELEASE };
+        (
+            FrameCount(i),
+            Duration::from_millis(500 + 1000 * i as u64),
+            event,
+        )
+    }))
+}
+
+#[cfg(feature = "test-support")]
+#[test]
+fn time_range_once_with_harness() {
+    let mut harness = DeterministicPlaybackHarness::new(Duration::from_secs(1));
+    let steps = harness.run(
+        BeginInputPlayback {
+            playback_strategy: PlaybackStrategy::TimeRangeOnce(
+                Duration::ZERO,
+                Duration::from_secs(2),
+            ),
+            source: Some(InputPlaybackSource::from_inputs(timed_keypresses())),
+            ..Default::default()
+        },
+        10,
+    );
+
+    // Time does not advance on the first update,
+    // and the range is covered in whole steps, so the press at 2.5 seconds is still played back
+    assert_eq!(
+        steps,
+        vec![
+            vec![],
+            vec![TEST_PRESS.into()],
+            vec![TEST_RELEASE.into()],
+            vec![TEST_PRESS.into()],
+        ]
+    );
+    assert!(harness.is_finished());
+    assert!(harness.step().is_empty());
+}
+
+#[cfg(feature = "test-support")]
+#[test]
+fn time_range_loop_with_harness() {
+    let mut harness = DeterministicPlaybackHarness::new(Duration::from_secs(1));
+    let steps = harness.run(
+        BeginInputPlayback {
+            playback_strategy: PlaybackStrategy::TimeRangeLoop(
+                Duration::ZERO,
+                Duration::from_secs(2),
+            ),
+            source: Some(InputPlaybackSource::from_inputs(timed_keypresses())),
+            ..Default::default()
+        },
+        8,
+    );
+
+    // Looping playback never finishes
+    assert_eq!(steps.len(), 8);
+    assert!(!harness.is_finished());
+    assert!(steps[0].is_empty());
+    let loop_events = vec![
+        vec![TEST_PRESS.into()],
+        vec![TEST_RELEASE.into()],
+        vec![TEST_PRESS.into()],
+    ];
+    assert_eq!(steps[1..4], loop_events);
+    assert_eq!(steps[4..7], loop_events);
+    assert_eq!(steps[7], loop_events[0]);
+}
+
+#[cfg(feature = "test-support")]
+#[test]
+fn collect_simple_playback() {
+    let mut app = playback_app();
+    let played = collect_playback(&mut app, simple_timestamped_input(), 3);
+
+    assert_eq!(played, vec![TEST_PRESS.into(), TEST_RELEASE.into()]);
+}
+
+#[cfg(feature = "test-support")]
+#[test]
+fn replay_against_mock_gamepad() {
+    use bevy::input::gamepad::{
+        GamepadButtonChangedEvent, GamepadConnection, GamepadConnectionEvent,
+    };
+    use leafwing_input_playback::test_support::register_gamepad;
+
+    let mut app = playback_app();
+    let gamepad = app.world_mut().spawn_empty().id();
+    register_gamepad(&mut app, gamepad);
+    app.update();
+    assert!(app.world().get::<Gamepad>(gamepad).is_some());
+
+    let press =
+        GamepadButtonChangedEvent::new(gamepad, GamepadButton::South, ButtonState::Pressed, 1.0);
+    let mut inputs = TimestampedInputs::default();
+    inputs.send(
+        FrameCount(0),
+        Duration::ZERO,
+        GamepadEvent::Button(press).into(),
+    );
+    inputs.send(
+        FrameCount(1),
+        Duration::ZERO,
+        GamepadEvent::Connection(GamepadConnectionEvent::new(
+            gamepad,
+            GamepadConnection::Disconnected,
+        ))
+        .into(),
+    );
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::FrameCount,
+        source: Some(InputPlaybackSource::from_inputs(inputs)),
+        ..Default::default()
+    });
+    app.world_mut().flush();
+
+    let mut reader = app.world().resource::<Events<GamepadEvent>>().get_cursor();
+    app.update();
+    let gamepad_events = app.world().resource::<Events<GamepadEvent>>();
+    let replayed: Vec<&GamepadEvent> = reader
+        .read(gamepad_events)
+        .filter(|event| matches!(event, GamepadEvent::Button(_)))
+        .collect();
+    assert_eq!(replayed, vec![&GamepadEvent::Button(press)]);
+
+    // Replayed connection events also connect and disconnect the gamepad
+    app.update();
+    assert!(app.world().get::<Gamepad>(gamepad).is_none());
+}
+
+#[cfg(feature = "test-support")]
+#[test]
+fn harness_reports_all_event_types() {
+    use bevy::input::mouse::AccumulatedMouseMotion;
+    use bevy::window::{Ime, WindowMoved};
+    use leafwing_input_playback::timestamped_input::InputEvent;
+
+    let motion = AccumulatedMouseMotion {
+        delta: Vec2::new(1., 2.),
+    };
+    let window_moved = WindowMoved {
+        window: Entity::PLACEHOLDER,
+        position: IVec2::new(10, 20),
+    };
+    let ime = Ime::Commit {
+        window: Entity::PLACEHOLDER,
+        value: "pasted".to_string(),
+    };
+    let recorded: Vec<InputEvent> = vec![
+        TEST_PRESS.into(),
+        motion.into(),
+        window_moved.into(),
+        ime.into(),
+    ];
+    let inputs = TimestampedInputs::from_timed(
+        recorded
+            .iter()
+            .cloned()
+            .map(|event| (FrameCount(0), Duration::ZERO, event)),
+    );
+
+    let mut harness = DeterministicPlaybackHarness::new(Duration::from_millis(100));
+    let steps = harness.run(
+        BeginInputPlayback {
+            playback_strategy: PlaybackStrategy::FrameCount,
+            source: Some(InputPlaybackSource::from_inputs(inputs)),
+            ..Default::default()
+        },
+        10,
+    );
+
+    assert_eq!(steps, vec![recorded]);
+}
+
+#[cfg(feature = "picking")]
+#[test]
+fn capture_and_replay_pointer_press() {
+    use bevy::picking::backend::HitData;
+    use bevy::picking::events::{Click, Down, Pointer, Up};
+    use bevy::picking::pointer::{Location, PointerButton, PointerId};
+    use bevy::render::camera::NormalizedRenderTarget;
+    use bevy::window::WindowRef;
+    use leafwing_input_playback::input_capture::{BeginInputCapture, EndInputCapture};
+
+    #[derive(Resource, Default)]
+    struct Presses(usize);
+
+    let mut app = playback_app();
+    app.add_plugins(InputCapturePlugin::default())
+        .add_event::<Pointer<Down>>()
+        .add_event::<Pointer<Up>>()
+        .add_event::<Pointer<Click>>()
+        .init_resource::<Presses>();
+    app.update();
+
+    let window = app
+        .world_mut()
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .single(app.world());
+    let camera = app.world_mut().spawn_empty().id();
+    let button = app
+        .world_mut()
+        .spawn_empty()
+        .observe(
+            |_trigger: Trigger<Pointer<Down>>, mut presses: ResMut<Presses>| {
+                presses.0 += 1;
+            },
+        )
+        .id();
+
+    app.world_mut().trigger(BeginInputCapture {
+        capture_pointer_events: true,
+        ..Default::default()
+    });
+    // Mock the press that a picking backend would have detected
+    app.world_mut().send_event(Pointer::new(
+        button,
+        PointerId::Mouse,
+        Location {
+            target: NormalizedRenderTarget::Window(
+                WindowRef::Primary.normalize(Some(window)).unwrap(),
+            ),
+            position: Vec2::new(10., 20.),
+        },
+        Down {
+            button: PointerButton::Primary,
+            hit: HitData::new(camera, 1.0, None, None),
+        },
+    ));
+    app.update();
+    app.world_mut().trigger(EndInputCapture);
+    app.world_mut().flush();
+    assert_eq!(app.world().resource::<TimestampedInputs>().len(), 1);
+    assert_eq!(app.world().resource::<Presses>().0, 0);
+
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::Time,
+        ..Default::default()
+    });
+    app.world_mut().flush();
+    app.update();
+
+    assert_eq!(app.world().resource::<Presses>().0, 1);
+    let mut down_events = app.world_mut().resource_mut::<Events<Pointer<Down>>>();
+    let replayed = down_events.drain().last().unwrap();
+    assert_eq!(replayed.target, button);
+    assert_eq!(replayed.pointer_location.position, Vec2::new(10., 20.));
+    assert_eq!(replayed.button, PointerButton::Primary);
+}
+
+#[cfg(feature = "file")]
+#[test]
+fn warn_on_zero_span_recording() {
+    use bevy::log::tracing_subscriber;
+    use bevy::log::tracing_subscriber::util::SubscriberInitExt;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    /// Collects everything that is logged, so that warnings can be inspected
+    #[derive(Clone, Default)]
+    struct LogBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for LogBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let path = std::env::temp_dir().join("leafwing_input_playback_zero_span.ron");
+    let mut inputs = TimestampedInputs::default();
+    // Every event is stuck at frame 0 and time 0, as in a partially-written recording
+    for event in [TEST_PRESS, TEST_RELEASE, TEST_PRESS] {
+        inputs.send(FrameCount(0), Duration::ZERO, event.into());
+    }
+    std::fs::write(&path, ron::to_string(&inputs).unwrap()).unwrap();
+
+    let logs = LogBuffer::default();
+    let subscriber = tracing_subscriber::fmt()
+        .with_writer({
+            let logs = logs.clone();
+            move || logs.clone()
+        })
+        .with_ansi(false)
+        .finish();
+
+    let begin_playback = |skip_validation| BeginInputPlayback {
+        source: Some(InputPlaybackSource::from_file(path.to_str().unwrap())),
+        skip_validation,
+        ..Default::default()
+    };
+    let guard = subscriber.set_default();
+    let mut app = playback_app();
+    app.world_mut().trigger(begin_playback(true));
+    app.world_mut().flush();
+    assert!(logs.0.lock().unwrap().is_empty());
+
+    app.world_mut().trigger(begin_playback(false));
+    app.world_mut().flush();
+    drop(guard);
+
+    let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+    assert!(logs.contains("WARN"));
+    assert!(logs.contains("All 3 events in the recording share the same frame and time"));
+}
+
+#[test]
+fn capture_and_playback_against_sim_tick() {
+    use leafwing_input_playback::input_capture::{BeginInputCapture, EndInputCapture};
+    use leafwing_input_playback::timestamped_input::SimTick;
+
+    let mut capture_app = App::new();
+    capture_app.add_plugins((
+        MinimalPlugins,
+        WindowPlugin::default(),
+        InputPlugin,
+        InputCapturePlugin::default(),
+    ));
+    capture_app.insert_resource(SimTick(100));
+    capture_app.world_mut().trigger(BeginInputCapture {
+        use_sim_tick: true,
+        ..Default::default()
+    });
+
+    // The simulation only ticks every third rendered frame
+    for frame in 0..9 {
+        if frame % 3 == 0 {
+            capture_app.world_mut().resource_mut::<SimTick>().0 += 1;
+        }
+        if frame == 4 {
+            capture_app.world_mut().send_event(TEST_PRESS);
+        }
+        if frame == 7 {
+            capture_app.world_mut().send_event(TEST_RELEASE);
+        }
+        capture_app.update();
+    }
+    capture_app.world_mut().trigger(EndInputCapture);
+    let inputs = capture_app
+        .world_mut()
+        .remove_resource::<TimestampedInputs>()
+        .unwrap();
+    let frames: Vec<FrameCount> = inputs.events.iter().map(|event| event.frame).collect();
+    assert_eq!(frames, vec![FrameCount(102), FrameCount(103)]);
+
+    let mut app = playback_app();
+    app.insert_resource(SimTick(100));
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::FrameCount,
+        source: Some(InputPlaybackSource::from_inputs(inputs)),
+        use_sim_tick: true,
+        ..Default::default()
+    });
+    app.world_mut().flush();
+
+    // Rendered frames pass, but the simulation has not reached the recorded ticks yet
+    for _ in 0..5 {
+        app.update();
+    }
+    assert_eq!(app.world().resource::<TimestampedInputs>().cursor, 0);
+
+    app.world_mut().resource_mut::<SimTick>().0 = 102;
+    app.update();
+    assert!(app
+        .world()
+        .resource::<ButtonInput<KeyCode>>()
+        .pressed(KeyCode::KeyF));
+
+    app.world_mut().resource_mut::<SimTick>().0 = 103;
+    app.update();
+    assert!(!app
+        .world()
+        .resource::<ButtonInput<KeyCode>>()
+        .pressed(KeyCode::KeyF));
+}
+
+#[test]
+fn immediate_input_update() {
+    use leafwing_input_playback::input_playback::playback_timestamped_input;
+
+    /// Whether the key was pressed, as seen by a system in `First`
+    #[derive(Resource, Default)]
+    struct PressedInFirst(bool);
+
+    for immediate_input_update in [false, true] {
+        let mut app = playback_app();
+        app.init_resource::<PressedInFirst>().add_systems(
+            First,
+            (|input: Res<ButtonInput<KeyCode>>, mut pressed: ResMut<PressedInFirst>| {
+                pressed.0 = input.pressed(KeyCode::KeyF);
+            })
+            .after(playback_timestamped_input)
+            .after(bevy::input::keyboard::keyboard_input_system),
+        );
+        app.world_mut().trigger(BeginInputPlayback {
+            playback_strategy: PlaybackStrategy::FrameCount,
+            source: Some(InputPlaybackSource::from_inputs(simple_timestamped_input())),
+            immediate_input_update,
+            ..Default::default()
+        });
+        app.world_mut().flush();
+
+        app.update();
+        assert_eq!(
+            app.world().resource::<PressedInFirst>().0,
+            immediate_input_update
+        );
+        assert!(app
+            .world()
+            .resource::<ButtonInput<KeyCode>>()
+            .just_pressed(KeyCode::KeyF));
+
+        app.update();
+        assert_eq!(
+            app.world().resource::<PressedInFirst>().0,
+            !immediate_input_update
+        );
+        assert!(app
+            .world()
+            .resource::<ButtonInput<KeyCode>>()
+            .just_released(KeyCode::KeyF));
+    }
+}
+
+#[test]
+fn per_frame_snapshots() {
+    let snapshots = complex_timestamped_input().per_frame_snapshots();
+
+    assert_eq!(snapshots.len(), 4);
+    assert_eq!(snapshots[&FrameCount(0)], vec![TEST_PRESS.into()]);
+    assert_eq!(snapshots[&FrameCount(2)].len(), 2);
+    assert_eq!(
+        snapshots.keys().copied().collect::<Vec<_>>(),
+        (0..4).map(FrameCount).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn cursor_origin() {
+    // Bevy measures cursor positions from the top-left corner of the window,
+    // so only recordings with a bottom-left origin need to be flipped
+    for (cursor_origin, expected_y) in [
+        (CursorOrigin::TopLeft, 20.0),
+        (CursorOrigin::BottomLeft, 700.0),
+    ] {
+        let mut app = playback_app();
+        let primary_window = app
+            .world_mut()
+            .query_filtered::<Entity, With<PrimaryWindow>>()
+            .single(app.world());
+        let height = app.world().get::<Window>(primary_window).unwrap().height();
+        assert_eq!(height, 720.0);
+
+        let mut inputs = TimestampedInputs::default();
+        inputs.send(
+            FrameCount(0),
+            Duration::ZERO,
+            CursorMoved {
+                window: primary_window,
+                position: Vec2::new(10.0, 20.0),
+                delta: Some(Vec2::new(1.0, 2.0)),
+            }
+            .into(),
+        );
+
+        app.world_mut().trigger(BeginInputPlayback {
+            playback_strategy: PlaybackStrategy::FrameCount,
+            source: Some(InputPlaybackSource::from_inputs(inputs)),
+            cursor_origin,
+            ..Default::default()
+        });
+        app.world_mut().flush();
+        app.update();
+
+        let cursor_events = app.world().resource::<Events<CursorMoved>>();
+        let cursor_event = cursor_events.iter_current_update_events().next().unwrap();
+        assert_eq!(cursor_event.position, Vec2::new(10.0, expected_y));
+        let expected_delta_y = if expected_y == 20.0 { 2.0 } else { -2.0 };
+        assert_eq!(cursor_event.delta, Some(Vec2::new(1.0, expected_delta_y)));
+
+        let window = app.world().get::<Window>(primary_window).unwrap();
+        assert_eq!(window.cursor_position(), Some(Vec2::new(10.0, expected_y)));
+    }
+}
+
+#[test]
+fn window_moved_round_trip() {
+    use bevy::window::{WindowMoved, WindowPosition};
+    use leafwing_input_playback::input_capture::{BeginInputCapture, EndInputCapture};
+    use leafwing_input_playback::timestamped_input::InputEvent;
+
+    let mut capture_app = App::new();
+    capture_app.add_plugins((
+        MinimalPlugins,
+        WindowPlugin::default(),
+        InputPlugin,
+        InputCapturePlugin::default(),
+    ));
+    capture_app
+        .world_mut()
+        .trigger(BeginInputCapture::default());
+    let recorded_window = capture_app
+        .world_mut()
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .single(capture_app.world());
+
+    capture_app.world_mut().send_event(WindowMoved {
+        window: recorded_window,
+        position: IVec2::new(100, 200),
+    });
+    capture_app.update();
+    capture_app.world_mut().trigger(EndInputCapture);
+    let mut inputs = capture_app
+        .world_mut()
+        .remove_resource::<TimestampedInputs>()
+        .unwrap();
+    assert_eq!(inputs.len(), 1);
+
+    let mut app = playback_app();
+    let primary_window = app
+        .world_mut()
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .single(app.world());
+    for event in inputs.events.iter_mut() {
+        if let InputEvent::WindowMoved(window_moved) = &mut event.input_event {
+            window_moved.window = primary_window;
+        }
+    }
+
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::FrameCount,
+        source: Some(InputPlaybackSource::from_inputs(inputs)),
+        ..Default::default()
+    });
+    app.world_mut().flush();
+    app.update();
+
+    let window_moved_events = app.world().resource::<Events<WindowMoved>>();
+    assert_eq!(window_moved_events.len(), 1);
+    let window = app.world().get::<Window>(primary_window).unwrap();
+    assert_eq!(window.position, WindowPosition::At(IVec2::new(100, 200)));
+}
+
+#[test]
+fn gated_playback() {
+    let mut app = playback_app();
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::Gated,
+        source: Some(InputPlaybackSource::from_inputs(complex_timestamped_input())),
+        ..Default::default()
+    });
+    app.world_mut().flush();
+
+    // The gate starts closed
+    app.update();
+    assert_eq!(app.world().resource::<Events<KeyboardInput>>().len(), 0);
+    app.insert_resource(PlaybackGate(false));
+    app.update();
+    assert_eq!(app.world().resource::<Events<KeyboardInput>>().len(), 0);
+
+    // Each opening of the gate plays back a single recorded frame
+    for expected in [1, 1, 2, 1] {
+        app.insert_resource(PlaybackGate(true));
+        app.update();
+        let input_events = app.world().resource::<Events<KeyboardInput>>();
+        assert_eq!(input_events.iter_current_update_events().count(), expected);
+        assert!(!app.world().resource::<PlaybackGate>().0);
+
+        // Nothing more is played until the gate is opened again
+        app.update();
+        let input_events = app.world().resource::<Events<KeyboardInput>>();
+        assert_eq!(input_events.iter_current_update_events().count(), 0);
+    }
+
+    let timestamped_input = app.world().resource::<TimestampedInputs>();
+    assert_eq!(timestamped_input.cursor, timestamped_input.len());
+}
+
+#[cfg(feature = "file")]
+#[test]
+fn reload_playback_file() {
+    use bevy::input::mouse::MouseButtonInput;
+    use leafwing_input_playback::input_playback::ReloadPlaybackFile;
+    use leafwing_input_playback::serde::PlaybackFilePath;
+
+    let path = std::env::temp_dir().join("leafwing_input_playback_reload.ron");
+    let path_str = path.to_str().unwrap();
+    let write_recording = |inputs: &TimestampedInputs| {
+        std::fs::write(&path, ron::to_string(inputs).unwrap()).unwrap();
+    };
+    write_recording(&simple_timestamped_input());
+
+    let mut app = playback_app();
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::FrameRangeOnce(FrameCount(0), FrameCount(10)),
+        source: Some(InputPlaybackSource::File(PlaybackFilePath::new(path_str))),
+        ..Default::default()
+    });
+    app.world_mut().flush();
+    app.update();
+    app.update();
+    assert_eq!(app.world().resource::<TimestampedInputs>().cursor, 2);
+
+    // Saving the file does nothing by itself, as it is not being watched
+    let mut edited = TimestampedInputs::default();
+    let click = MouseButtonInput {
+        button: MouseButton::Left,
+        state: ButtonState::Pressed,
+        window: Entity::PLACEHOLDER,
+    };
+    edited.send(FrameCount(0), Duration::ZERO, click.into());
+    write_recording(&edited);
+    app.update();
+    assert_eq!(app.world().resource::<Events<MouseButtonInput>>().len(), 0);
+
+    // Reloading restarts playback with the new recording
+    app.world_mut().trigger(ReloadPlaybackFile);
+    app.world_mut().flush();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(*app.world().resource::<TimestampedInputs>(), edited);
+    assert_eq!(
+        *app.world().resource::<PlaybackProgress>(),
+        PlaybackProgress::default()
+    );
+
+    app.update();
+    let mouse_events = app.world().resource::<Events<MouseButtonInput>>();
+    assert_eq!(mouse_events.iter_current_update_events().count(), 1);
+}
+
+#[test]
+fn playback_in_fixed_timestep() {
+    /// The number of keyboard events read by each run of `FixedUpdate`
+    #[derive(Resource, Default)]
+    struct EventsPerFixedStep(Vec<usize>);
+
+    let period = Time::<Fixed>::default().timestep();
+    let mut inputs = TimestampedInputs::default();
+    for step in 1..=6 {
+        let event = if step % 2 == 1 {
+            TEST_PRESS
+        } else {
+            TEST_RELEASE
+        };
+        inputs.send(FrameCount(step), period * step, event.into());
+    }
+
+    let mut app = playback_app();
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(period))
+        .init_resource::<EventsPerFixedStep>()
+        .add_systems(
+            FixedUpdate,
+            |mut events: EventReader<KeyboardInput>, mut counts: ResMut<EventsPerFixedStep>| {
+                counts.0.push(events.read().count());
+            },
+        );
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::Time,
+        source: Some(InputPlaybackSource::from_inputs(inputs)),
+        fixed_timestep: true,
+        ..Default::default()
+    });
+    app.world_mut().flush();
+
+    // The first update has a delta of zero, so no fixed steps are run
+    for _ in 0..8 {
+        app.update();
+    }
+
+    // Each fixed step sees exactly the events recorded for it
+    assert_eq!(
+        app.world().resource::<EventsPerFixedStep>().0,
+        vec![1, 1, 1, 1, 1, 1, 0]
+    );
+}
+
+#[cfg(feature = "file")]
+#[test]
+fn concatenate_recordings_from_files() {
+    use leafwing_input_playback::input_playback::TimestampedInputsError;
+    use leafwing_input_playback::timestamped_input::Modifiers;
+
+    let first_path = std::env::temp_dir().join("leafwing_input_playback_playlist_first.ron");
+    let second_path = std::env::temp_dir().join("leafwing_input_playback_playlist_second.ron");
+    let missing_path = std::env::temp_dir().join("leafwing_input_playback_playlist_missing.ron");
+
+    let mut first = TimestampedInputs::default();
+    first.send(FrameCount(0), Duration::ZERO, TEST_PRESS.into());
+    first.send(FrameCount(2), Duration::from_secs(2), TEST_RELEASE.into());
+    let mut second = complex_timestamped_input();
+    let ctrl = Modifiers {
+        ctrl: true,
+        ..Default::default()
+    };
+    second.events[1].modifiers = Some(ctrl);
+    std::fs::write(&first_path, ron::to_string(&first).unwrap()).unwrap();
+    std::fs::write(&second_path, ron::to_string(&second).unwrap()).unwrap();
+
+    let playlist = TimestampedInputs::from_files(&[&first_path, &second_path]);
+    let missing = TimestampedInputs::from_files(&[&first_path, &missing_path]);
+    std::fs::remove_file(&first_path).unwrap();
+    std::fs::remove_file(&second_path).unwrap();
+
+    let playlist = playlist.unwrap();
+    assert_eq!(playlist.len(), first.len() + second.len());
+    // The second recording begins one recorded frame after the first one ends
+    let frames: Vec<u32> = playlist.events.iter().map(|event| event.frame.0).collect();
+    assert_eq!(frames, vec![0, 2, 3, 4, 5, 5, 6]);
+    let times: Vec<u64> = playlist
+        .events
+        .iter()
+        .map(|event| event.time_since_startup.as_secs())
+        .collect();
+    assert_eq!(times, vec![0, 2, 3, 4, 5, 6, 6]);
+    let inputs: Vec<_> = playlist
+        .events
+        .iter()
+        .map(|event| event.input_event.clone())
+        .collect();
+    let expected: Vec<_> = first
+        .events
+        .iter()
+        .chain(second.events.iter())
+        .map(|event| event.input_event.clone())
+        .collect();
+    assert_eq!(inputs, expected);
+    // Modifier state is kept when recordings are concatenated
+    let modifiers: Vec<Option<Modifiers>> = playlist
+        .events
+        .iter()
+        .map(|event| event.modifiers)
+        .collect();
+    assert_eq!(
+        modifiers,
+        vec![None, None, None, Some(ctrl), None, None, None]
+    );
+
+    match missing {
+        Err(TimestampedInputsError::File(path, error)) => {
+            assert_eq!(path, missing_path);
+            assert!(matches!(*error, TimestampedInputsError::Fs(_)));
+        }
+        other => panic!("expected a missing file error, found {other:?}"),
+    }
+}
+
+#[test]
+fn rescale_to_source_frame_rate() {
+    const FRAME_DURATION: Duration = Duration::from_millis(5);
+
+    // Captured at 30 fps, so that the release happens after 2 seconds
+    let mut inputs = TimestampedInputs::default();
+    inputs.send(FrameCount(0), Duration::ZERO, TEST_PRESS.into());
+    inputs.send(FrameCount(60), Duration::from_secs(2), TEST_RELEASE.into());
+
+    // Returns the number of updates taken to play back the whole recording at 200 fps
+    let updates_to_finish = |playback_strategy: PlaybackStrategy, source_fps: Option<f32>| {
+        let mut app = playback_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(FRAME_DURATION));
+        app.world_mut().trigger(BeginInputPlayback {
+            playback_strategy,
+            source: Some(InputPlaybackSource::from_inputs(inputs.clone())),
+            source_fps,
+            ..Default::default()
+        });
+        app.world_mut().flush();
+
+        let mut updates = 0;
+        while app.world().resource::<TimestampedInputs>().cursor < 2 {
+            app.update();
+            updates += 1;
+            assert!(updates < 1000);
+        }
+        updates
+    };
+
+    // Without rescaling, the recording is replayed far too quickly
+    assert_eq!(updates_to_finish(PlaybackStrategy::FrameCount, None), 61);
+
+    // The replay takes approximately the recorded 2 seconds
+    let replay_duration =
+        FRAME_DURATION * updates_to_finish(PlaybackStrategy::RecordedFrameRate, Some(30.));
+    assert!(replay_duration >= Duration::from_secs(2));
+    assert!(replay_duration <= Duration::from_secs(2) + FRAME_DURATION * 2);
+
+    // The explicit frame rate takes precedence over the one estimated from the recording
+    let replay_duration =
+        FRAME_DURATION * updates_to_finish(PlaybackStrategy::RecordedFrameRate, Some(60.));
+    assert!(replay_duration >= Duration::from_secs(1));
+    assert!(replay_duration <= Duration::from_secs(1) + FRAME_DURATION * 2);
+
+    // The source frame rate does not affect frame-count playback
+    assert_eq!(
+        updates_to_finish(PlaybackStrategy::FrameCount, Some(30.)),
+        61
+    );
+}
+
+#[cfg(feature = "file")]
+#[test]
+fn playback_from_streaming_source() {
+    use leafwing_input_playback::serde::{append_to_streamed_recording, StreamingPlaybackSource};
+
+    const CHUNK_SIZE: usize = 16;
+
+    let mut inputs = TimestampedInputs::default();
+    for frame in 0..1000 {
+        let event = if frame % 2 == 0 {
+            TEST_PRESS
+        } else {
+            TEST_RELEASE
+        };
+        inputs.send(
+            FrameCount(frame),
+            Duration::from_millis(frame as u64 * 16),
+            event.into(),
+        );
+    }
+    let path = std::env::temp_dir().join("leafwing_input_playback_streaming_source.ron");
+    append_to_streamed_recording(&inputs.events, &path, true, false).unwrap();
+
+    let mut app = playback_app();
+    app.insert_resource(StreamingPlaybackSource::open(&path, CHUNK_SIZE).unwrap());
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::FrameCount,
+        source: Some(InputPlaybackSource::from_inputs(
+            TimestampedInputs::default(),
+        )),
+        ..Default::default()
+    });
+    app.world_mut().flush();
+
+    let mut played = Vec::new();
+    let mut reader = app.world().resource::<Events<KeyboardInput>>().get_cursor();
+    for _ in 0..1010 {
+        app.update();
+        // The buffer never holds more than a chunk of the recording at once
+        assert!(app.world().resource::<TimestampedInputs>().len() <= CHUNK_SIZE);
+        let keyboard_events = app.world().resource::<Events<KeyboardInput>>();
+        played.extend(reader.read(keyboard_events).map(|event| event.state));
+    }
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(app
+        .world()
+        .resource::<StreamingPlaybackSource>()
+        .is_exhausted());
+    let expected: Vec<ButtonState> = inputs
+        .events
+        .iter()
+        .map(|event| match &event.input_event {
+            leafwing_input_playback::timestamped_input::InputEvent::Keyboard(event) => event.state,
+            _ => unreachable!(),
+        })
+        .collect();
+    assert_eq!(played, expected);
+}
+
+#[cfg(feature = "file")]
+#[test]
+fn streaming_source_rejects_unsupported_strategies() {
+    use leafwing_input_playback::serde::{append_to_streamed_recording, StreamingPlaybackSource};
+
+    let mut inputs = TimestampedInputs::default();
+    inputs.send(FrameCount(0), Duration::ZERO, TEST_PRESS.into());
+    inputs.send(
+        FrameCount(1),
+        Duration::from_millis(16),
+        TEST_RELEASE.into(),
+    );
+    let path = std::env::temp_dir().join("leafwing_input_playback_streaming_strategies.ron");
+    append_to_streamed_recording(&inputs.events, &path, true, false).unwrap();
+
+    // These strategies measure the recording from its first event, which is discarded once played
+    for playback_strategy in [
+        PlaybackStrategy::RecordedFrameRate,
+        PlaybackStrategy::FrameRangeLoop(FrameCount(0), FrameCount(2)),
+        PlaybackStrategy::TimeRangeOnce(Duration::ZERO, Duration::from_secs(1)),
+    ] {
+        let mut app = playback_app();
+        app.insert_resource(StreamingPlaybackSource::open(&path, 1).unwrap());
+        app.world_mut().trigger(BeginInputPlayback {
+            playback_strategy,
+            source: Some(InputPlaybackSource::from_inputs(
+                TimestampedInputs::default(),
+            )),
+            ..Default::default()
+        });
+        app.world_mut().flush();
+        app.update();
+
+        assert_eq!(
+            *app.world().resource::<PlaybackStrategy>(),
+            PlaybackStrategy::Paused
+        );
+        assert_eq!(app.world().resource::<Events<KeyboardInput>>().len(), 0);
+    }
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn paste_round_trip() {
+    use bevy::window::Ime;
+    use leafwing_input_playback::input_capture::{BeginInputCapture, EndInputCapture};
+    use leafwing_input_playback::timestamped_input::InputEvent;
+
+    let mut capture_app = App::new();
+    capture_app.add_plugins((
+        MinimalPlugins,
+        WindowPlugin::default(),
+        InputPlugin,
+        InputCapturePlugin::default(),
+    ));
+    capture_app
+        .world_mut()
+        .trigger(BeginInputCapture::default());
+    let window = capture_app
+        .world_mut()
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .single(capture_app.world());
+
+    // A burst of typed characters, followed by a very long pasted string, all in a single frame
+    let typed = "the quick brown fox";
+    let pasted: String = (0..10_000)
+        .map(|i| char::from(b'a' + (i * 7 % 26) as u8))
+        .collect();
+    for character in typed.chars() {
+        capture_app.world_mut().send_event(KeyboardInput {
+            logical_key: Key::Character(character.to_string().into()),
+            key_code: KeyCode::KeyA,
+            state: ButtonState::Pressed,
+            window,
+            repeat: false,
+        });
+    }
+    capture_app.world_mut().send_event(Ime::Commit {
+        window,
+        value: pasted.clone(),
+    });
+    capture_app.update();
+    capture_app.world_mut().trigger(EndInputCapture);
+    let inputs = capture_app
+        .world_mut()
+        .remove_resource::<TimestampedInputs>()
+        .unwrap();
+    assert_eq!(inputs.len(), typed.len() + 1);
+    assert!(matches!(
+        inputs.events.last().unwrap().input_event,
+        InputEvent::Ime(Ime::Commit { .. })
+    ));
+
+    let mut app = playback_app();
+    app.world_mut().trigger(BeginInputPlayback {
+        playback_strategy: PlaybackStrategy::FrameCount,
+        source: Some(InputPlaybackSource::from_inputs(inputs)),
+        ..Default::default()
+    });
+    app.world_mut().flush();
+    app.update();
+
+    // Every character is replayed in the same frame, in the order it was typed
+    let keyboard_events = app.world().resource::<Events<KeyboardInput>>();
+    let replayed_typed: String = keyboard_events
+        .iter_current_update_events()
+        .map(|event| match &event.logical_key {
+            Key::Character(character) => character.to_string(),
+            _ => unreachable!(),
+        })
+        .collect();
+    assert_eq!(replayed_typed, typed);
+
+    let ime_events: Vec<&Ime> = app
+        .world()
+        .resource::<Events<Ime>>()
+        .iter_current_update_events()
+        .collect();
+    assert_eq!(ime_events.len(), 1);
+    match ime_events[0] {
+        Ime::Commit { value, .. } => assert_eq!(*value, pasted),
+        other => panic!("expected the pasted text to be replayed, found {other:?}"),
+    }
+}